@@ -0,0 +1,268 @@
+//! Axis-aligned bounding box in a 2-Dimensional vector space.
+//!
+//! Provides a bounding box struct for the computational geometry library [cg_library](crate).
+
+use crate::point2d::Point;
+use crate::scalar::Scalar;
+use std::fmt;
+
+/// An axis-aligned bounding box in a 2D vector space.
+///
+/// The box consists of its corner with the smallest and its corner with the largest coordinates,
+/// its edges are part of the box. It is used for cheap rejection tests, since two shapes can only
+/// intersect if their boxes [overlap](Aabb::overlaps). Every shape exposes its box, like
+/// [LineSegment](crate::linesegment2d::LineSegment::bounding_box) and
+/// [Polygon](crate::polygon2d::Polygon::bounding_box). Its coordinates are of any
+/// [scalar](crate::scalar::Scalar) type, [Aabb2D] is the box with `f64` coordinates.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::aabb2d::Aabb;
+/// use cg_library::point2d::Point;
+/// let b: Aabb<i64> = Aabb::new(Point { x: 2, y: 0 }, Point { x: 0, y: 3 });
+/// assert_eq!(Point { x: 0, y: 0 }, b.min);
+/// assert_eq!(6, b.area());
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Aabb<T> {
+    /// The corner with the smallest coordinates.
+    pub min: Point<T>,
+    /// The corner with the largest coordinates.
+    pub max: Point<T>,
+}
+
+/// An axis-aligned bounding box in a 2D vector space with `f64` coordinates.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::aabb2d::Aabb2D;
+/// use cg_library::point2d::Point2D;
+/// let b: Aabb2D = Aabb2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 1.0 });
+/// assert_eq!(Point2D { x: 1.0, y: 0.5 }, b.center());
+/// ```
+pub type Aabb2D = Aabb<f64>;
+
+/// This trait allows boxes to be compared by their corners.
+impl<T: Scalar> PartialEq for Aabb<T> {
+    fn eq(&self, other: &Aabb<T>) -> bool {
+        self.min == other.min && self.max == other.max
+    }
+}
+
+/// Returns the smaller of two partially ordered values.
+fn min<T: Scalar>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Returns the larger of two partially ordered values.
+fn max<T: Scalar>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+impl<T: Scalar> Aabb<T> {
+    /// Returns the smallest box that contains two corners in any order.
+    pub fn new(p: Point<T>, q: Point<T>) -> Aabb<T> {
+        Aabb {
+            min: Point {
+                x: min(p.x, q.x),
+                y: min(p.y, q.y),
+            },
+            max: Point {
+                x: max(p.x, q.x),
+                y: max(p.y, q.y),
+            },
+        }
+    }
+
+    /// Returns the smallest box that contains all points, or `None` if there are no points.
+    pub fn from_points<'a, I>(points: I) -> Option<Aabb<T>>
+    where
+        I: IntoIterator<Item = &'a Point<T>>,
+        T: 'a,
+    {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Aabb::new(*first, *first), |b, p| b.expand_to(p)))
+    }
+
+    /// Returns the width of the box.
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    /// Returns the height of the box.
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y
+    }
+
+    /// Returns the area of the box.
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    /// Returns the center point of the box.
+    ///
+    /// The center of integer boxes is truncated.
+    pub fn center(&self) -> Point<T> {
+        self.min + (self.max - self.min) / (T::one() + T::one())
+    }
+
+    /// Returns the smallest box that contains both boxes.
+    pub fn union(&self, other: &Aabb<T>) -> Aabb<T> {
+        self.expand_to(&other.min).expand_to(&other.max)
+    }
+
+    /// Returns the box that both boxes share, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Aabb<T>) -> Option<Aabb<T>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Aabb {
+            min: Point {
+                x: max(self.min.x, other.min.x),
+                y: max(self.min.y, other.min.y),
+            },
+            max: Point {
+                x: min(self.max.x, other.max.x),
+                y: min(self.max.y, other.max.y),
+            },
+        })
+    }
+
+    /// Returns `true` iff both boxes share at least one point, touching edges included.
+    pub fn overlaps(&self, other: &Aabb<T>) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+
+    /// Returns `true` iff a point lies inside or on the edges of the box.
+    pub fn contains_point(&self, p: &Point<T>) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    /// Returns `true` iff the other box lies completely inside of the box.
+    pub fn contains_box(&self, other: &Aabb<T>) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    /// Returns the box grown by a margin on every side.
+    ///
+    /// A negative margin shrinks the box, but not beyond its center.
+    pub fn expand(&self, margin: T) -> Aabb<T> {
+        let center = self.center();
+        let shift = |low: T, high: T, c: T| (min(low - margin, c), max(high + margin, c));
+        let (min_x, max_x) = shift(self.min.x, self.max.x, center.x);
+        let (min_y, max_y) = shift(self.min.y, self.max.y, center.y);
+        Aabb {
+            min: Point { x: min_x, y: min_y },
+            max: Point { x: max_x, y: max_y },
+        }
+    }
+
+    /// Returns the smallest box that contains the box and a point.
+    pub fn expand_to(&self, p: &Point<T>) -> Aabb<T> {
+        Aabb {
+            min: Point {
+                x: min(self.min.x, p.x),
+                y: min(self.min.y, p.y),
+            },
+            max: Point {
+                x: max(self.max.x, p.x),
+                y: max(self.max.y, p.y),
+            },
+        }
+    }
+}
+
+/// This trait allows a box to be displayed in the form of `[(x1,y1),(x2,y2)]`.
+impl<T: Scalar> fmt::Display for Aabb<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{},{}]", self.min, self.max)
+    }
+}
+
+#[cfg(test)]
+mod test_aabb2d {
+    use super::*;
+    use crate::point2d::Point2D;
+
+    #[test]
+    fn test_new() {
+        let b: Aabb<i64> = Aabb::new(Point { x: 3, y: -1 }, Point { x: -1, y: 2 });
+        assert_eq!(Point { x: -1, y: -1 }, b.min);
+        assert_eq!(Point { x: 3, y: 2 }, b.max);
+        assert_eq!(4, b.width());
+        assert_eq!(3, b.height());
+        assert_eq!(12, b.area());
+        assert_eq!(Point { x: 1, y: 0 }, b.center());
+        assert_eq!("[(-1,-1),(3,2)]", b.to_string());
+
+        let points = [
+            Point { x: 0, y: 5 },
+            Point { x: 2, y: 1 },
+            Point { x: -3, y: 2 },
+        ];
+        let b = Aabb::from_points(&points).unwrap();
+        assert_eq!(Aabb::new(Point { x: -3, y: 1 }, Point { x: 2, y: 5 }), b);
+        assert_eq!(None, Aabb::<i64>::from_points(&[]));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let b1: Aabb2D = Aabb2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let b2: Aabb2D = Aabb2D::new(Point2D { x: 1.0, y: 1.0 }, Point2D { x: 3.0, y: 4.0 });
+        let b3: Aabb2D = Aabb2D::new(Point2D { x: 3.0, y: 2.0 }, Point2D { x: 5.0, y: 5.0 });
+
+        assert_eq!(
+            Aabb2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 3.0, y: 4.0 }),
+            b1.union(&b2)
+        );
+        assert_eq!(
+            Some(Aabb2D::new(
+                Point2D { x: 1.0, y: 1.0 },
+                Point2D { x: 2.0, y: 2.0 }
+            )),
+            b1.intersection(&b2)
+        );
+        assert!(b1.overlaps(&b2));
+        assert!(!b1.overlaps(&b3));
+        assert_eq!(None, b1.intersection(&b3));
+
+        // Touching edges overlap in a degenerate box
+        assert!(b2.overlaps(&b3));
+        assert_eq!(0.0, b2.intersection(&b3).unwrap().area());
+    }
+
+    #[test]
+    fn test_containment() {
+        let b: Aabb2D = Aabb2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        assert!(b.contains_point(&Point2D { x: 1.0, y: 2.0 }));
+        assert!(!b.contains_point(&Point2D { x: 1.0, y: 2.5 }));
+
+        let inner = b.expand(-0.5);
+        assert_eq!(
+            Aabb2D::new(Point2D { x: 0.5, y: 0.5 }, Point2D { x: 1.5, y: 1.5 }),
+            inner
+        );
+        assert!(b.contains_box(&inner));
+        assert!(!inner.contains_box(&b));
+        assert!(b.expand(1.0).contains_point(&Point2D { x: -1.0, y: 3.0 }));
+        assert_eq!(0.0, b.expand(-2.0).area());
+        assert!(b
+            .expand_to(&Point2D { x: 4.0, y: -1.0 })
+            .contains_point(&Point2D { x: 3.0, y: 0.0 }));
+    }
+}
@@ -0,0 +1,240 @@
+//! Distances in a 2-Dimensional vector space.
+//!
+//! Provides distance and closest-point queries between the primitives of the computational
+//! geometry library [cg_library](crate).
+
+use crate::error::CgError;
+use crate::line2d::Line;
+use crate::linesegment2d::{LineSegment, SegmentIntersection};
+use crate::point2d::Point;
+use crate::polygon2d::Polygon;
+use crate::scalar::{Field, Scalar};
+use num_traits::Float;
+
+/// The closest points of two primitives and their distance.
+///
+/// The point `first` lies on the first primitive of the query and the point `second` on the
+/// second one. If the primitives intersect, both points are the same and the distance is zero.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::distance2d::point_segment;
+/// use cg_library::linesegment2d::LineSegment2D;
+/// use cg_library::point2d::Point2D;
+///
+/// let s = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 0.0 });
+/// let closest = point_segment(&Point2D { x: 5.0, y: 4.0 }, &s);
+/// assert_eq!(Point2D { x: 2.0, y: 0.0 }, closest.second);
+/// assert_eq!(5.0, closest.distance);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Closest<T> {
+    /// The euclidean distance of both points.
+    pub distance: T,
+
+    /// The closest point on the first primitive.
+    pub first: Point<T>,
+
+    /// The closest point on the second primitive.
+    pub second: Point<T>,
+}
+
+/// This trait allows closest points to be compared by their points and distance.
+impl<T: Scalar> PartialEq for Closest<T> {
+    fn eq(&self, other: &Closest<T>) -> bool {
+        self.distance == other.distance && self.first == other.first && self.second == other.second
+    }
+}
+
+impl<T: Scalar + Float> Closest<T> {
+    /// Returns the closest points with their distance.
+    fn new(first: Point<T>, second: Point<T>) -> Closest<T> {
+        Closest {
+            distance: first.distance_to(&second),
+            first,
+            second,
+        }
+    }
+
+    /// Returns the same closest points for the primitives in swapped order.
+    fn swap(self) -> Closest<T> {
+        Closest {
+            distance: self.distance,
+            first: self.second,
+            second: self.first,
+        }
+    }
+}
+
+/// Returns the closest point of a line to a point, which is the foot of the perpendicular.
+///
+/// The line is given by its implicit equation, so vertical lines need no special case. A line
+/// without a normal, like the [zero](Line::is_zero) line of a degenerate segment, results in a
+/// [degenerate geometry error](CgError::DegenerateGeometry).
+pub fn point_line<T: Scalar + Float>(p: &Point<T>, l: &Line<T>) -> Result<Closest<T>, CgError> {
+    let normal = l.normal();
+    if normal.norm_squared() == T::zero() {
+        return Err(CgError::DegenerateGeometry(format!(
+            "the line {l} has no normal"
+        )));
+    }
+    let foot = *p - normal * (l.evaluate(p) / normal.norm_squared());
+    Ok(Closest {
+        distance: l.distance_to(p),
+        first: *p,
+        second: foot,
+    })
+}
+
+/// Returns the closest point of a segment from `a` to `b` to a point.
+///
+/// The foot of the perpendicular is clamped to the endpoints, a segment of zero length is its
+/// only point.
+fn closest_on<T: Scalar + Float>(p: &Point<T>, a: &Point<T>, b: &Point<T>) -> Point<T> {
+    let direction = *b - *a;
+    let length = direction.norm_squared();
+    if length == T::zero() {
+        return *a;
+    }
+    let t = ((*p - *a).dot(direction) / length)
+        .max(T::zero())
+        .min(T::one());
+    *a + direction * t
+}
+
+/// Returns the closest point of a segment to a point.
+///
+/// The closest point is either the foot of the perpendicular or one of the endpoints.
+pub fn point_segment<T: Scalar + Float>(p: &Point<T>, s: &LineSegment<T>) -> Closest<T> {
+    Closest::new(*p, closest_on(p, &s.p1, &s.p2))
+}
+
+/// Returns the closest points of two segments.
+///
+/// If the segments intersect, the closest point is their intersection point or the start of
+/// their overlap. Otherwise one of the closest points is an endpoint of its segment.
+pub fn segment_segment<T: Field + Float>(s: &LineSegment<T>, other: &LineSegment<T>) -> Closest<T> {
+    match s.intersects(other) {
+        SegmentIntersection::Point(p) => return Closest::new(p, p),
+        SegmentIntersection::Overlap(overlap) => return Closest::new(overlap.p1, overlap.p1),
+        SegmentIntersection::None => {}
+    }
+
+    [
+        point_segment(&s.p1, other),
+        point_segment(&s.p2, other),
+        point_segment(&other.p1, s).swap(),
+        point_segment(&other.p2, s).swap(),
+    ]
+    .into_iter()
+    .min_by(|a, b| a.distance.canonical_cmp(&b.distance))
+    .unwrap()
+}
+
+/// Returns the closest point on the boundary of a polygon to a point.
+///
+/// Only the boundary is considered, so points inside of the polygon have a distance too.
+pub fn point_polygon<T: Scalar + Float>(p: &Point<T>, poly: &Polygon<T>) -> Closest<T> {
+    poly.points
+        .windows(2)
+        .map(|edge| Closest::new(*p, closest_on(p, &edge[0], &edge[1])))
+        .min_by(|a, b| a.distance.canonical_cmp(&b.distance))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test_distance2d {
+    use super::*;
+    use crate::line2d::Line2D;
+    use crate::linesegment2d::LineSegment2D;
+    use crate::point2d::Point2D;
+    use crate::polygon2d::Polygon2D;
+
+    #[test]
+    fn test_point_line() {
+        let l =
+            Line2D::from_point_and_point(Point2D { x: 1.0, y: 0.0 }, Point2D { x: 1.0, y: 5.0 });
+        let closest = point_line(&Point2D { x: 4.0, y: 2.0 }, &l).unwrap();
+        assert_eq!(3.0, closest.distance);
+        assert_eq!(Point2D { x: 4.0, y: 2.0 }, closest.first);
+        assert_eq!(Point2D { x: 1.0, y: 2.0 }, closest.second);
+
+        let l =
+            Line2D::from_point_and_point(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let closest = point_line(&Point2D { x: 0.0, y: 2.0 }, &l).unwrap();
+        assert_eq!(Point2D { x: 1.0, y: 1.0 }, closest.second);
+        assert!((closest.distance - 2.0.sqrt()).abs() < 1e-15);
+
+        // The line of a degenerate segment has no foot of the perpendicular
+        let s = LineSegment2D::new(Point2D { x: 1.0, y: 1.0 }, Point2D { x: 1.0, y: 1.0 });
+        assert!(matches!(
+            point_line(&Point2D { x: 0.0, y: 2.0 }, &s.line()),
+            Err(CgError::DegenerateGeometry(_))
+        ));
+    }
+
+    #[test]
+    fn test_point_segment() {
+        // The endpoints are sorted, the closest point is still the nearest endpoint
+        let s = LineSegment2D::new(Point2D { x: 2.0, y: 2.0 }, Point2D { x: 0.0, y: 0.0 });
+        let closest = point_segment(&Point2D { x: -1.0, y: -1.0 }, &s);
+        assert_eq!(Point2D { x: 0.0, y: 0.0 }, closest.second);
+        assert_eq!(2.0.sqrt(), closest.distance);
+
+        let closest = point_segment(&Point2D { x: 2.0, y: 0.0 }, &s);
+        assert_eq!(Point2D { x: 1.0, y: 1.0 }, closest.second);
+
+        let closest = point_segment(&Point2D { x: 1.0, y: 1.0 }, &s);
+        assert_eq!(0.0, closest.distance);
+    }
+
+    #[test]
+    fn test_segment_segment() {
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 0.0 });
+        let s2 = LineSegment2D::new(Point2D { x: 1.0, y: 3.0 }, Point2D { x: 2.0, y: 1.0 });
+        let closest = segment_segment(&s1, &s2);
+        assert_eq!(1.0, closest.distance);
+        assert_eq!(Point2D { x: 2.0, y: 0.0 }, closest.first);
+        assert_eq!(Point2D { x: 2.0, y: 1.0 }, closest.second);
+
+        let swapped = segment_segment(&s2, &s1);
+        assert_eq!(closest.first, swapped.second);
+        assert_eq!(closest.second, swapped.first);
+
+        let s3 = LineSegment2D::new(Point2D { x: 1.0, y: -1.0 }, Point2D { x: 3.0, y: 1.0 });
+        let closest = segment_segment(&s1, &s3);
+        assert_eq!(0.0, closest.distance);
+        assert_eq!(Point2D { x: 2.0, y: 0.0 }, closest.first);
+
+        // Parallel segments
+        let s4 = LineSegment2D::new(Point2D { x: 5.0, y: 2.0 }, Point2D { x: 6.0, y: 2.0 });
+        let closest = segment_segment(&s1, &s4);
+        assert_eq!(Point2D { x: 4.0, y: 0.0 }, closest.first);
+        assert_eq!(Point2D { x: 5.0, y: 2.0 }, closest.second);
+    }
+
+    #[test]
+    fn test_point_polygon() {
+        let square = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 4.0, y: 0.0 },
+            Point2D { x: 4.0, y: 4.0 },
+            Point2D { x: 0.0, y: 4.0 },
+            Point2D { x: 0.0, y: 0.0 },
+        ]);
+        let closest = point_polygon(&Point2D { x: 1.0, y: 2.0 }, &square);
+        assert_eq!(1.0, closest.distance);
+        assert_eq!(Point2D { x: 0.0, y: 2.0 }, closest.second);
+
+        let closest = point_polygon(&Point2D { x: 7.0, y: 8.0 }, &square);
+        assert_eq!(5.0, closest.distance);
+        assert_eq!(Point2D { x: 4.0, y: 4.0 }, closest.second);
+
+        let nan = Point2D {
+            x: f64::NAN,
+            y: 8.0,
+        };
+        assert!(point_polygon(&nan, &square).distance.is_nan());
+    }
+}
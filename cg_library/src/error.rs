@@ -0,0 +1,95 @@
+//! Errors of the computational geometry library [cg_library](crate).
+//!
+//! Provides the error type, that the fallible `try_` constructors and readers return instead of
+//! panicking.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// An error of the computational geometry library.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::error::CgError;
+/// use cg_library::point2d::Point2D;
+///
+/// let result = Point2D::try_new(f64::NAN, 1.0);
+/// assert!(matches!(result, Err(CgError::NanCoordinate)));
+/// ```
+#[derive(Debug)]
+pub enum CgError {
+    /// A file could not be read or written.
+    Io(io::Error),
+
+    /// A line of a file could not be parsed, the line number starts at one.
+    Parse {
+        /// The number of the line, starting at one.
+        line: usize,
+        /// The description of the problem.
+        message: String,
+    },
+
+    /// A string does not have the format of the type it is parsed into.
+    Format(String),
+
+    /// The geometry is degenerate, like a polygon with less than three points.
+    DegenerateGeometry(String),
+
+    /// A coordinate is not a number.
+    NanCoordinate,
+
+    /// A coordinate is infinite, but needs to be converted to an exact rational.
+    InfiniteCoordinate,
+}
+
+/// This trait allows an error to be displayed as a sentence.
+impl fmt::Display for CgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CgError::Io(e) => write!(f, "I/O error: {e}"),
+            CgError::Parse { line, message } => write!(f, "Parse error in line {line}: {message}"),
+            CgError::Format(message) => write!(f, "Invalid format: {message}"),
+            CgError::DegenerateGeometry(message) => write!(f, "Degenerate geometry: {message}"),
+            CgError::NanCoordinate => write!(f, "A coordinate is NaN"),
+            CgError::InfiniteCoordinate => write!(f, "A coordinate is infinite"),
+        }
+    }
+}
+
+/// This trait allows the error to be used with `?` and as the source of other errors.
+impl Error for CgError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CgError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// This trait allows I/O errors to be converted with `?`.
+impl From<io::Error> for CgError {
+    fn from(e: io::Error) -> Self {
+        CgError::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod test_error {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let e = CgError::Parse {
+            line: 3,
+            message: String::from("expected 4 values"),
+        };
+        assert_eq!("Parse error in line 3: expected 4 values", e.to_string());
+        assert_eq!("A coordinate is NaN", CgError::NanCoordinate.to_string());
+
+        let e: CgError = io::Error::new(io::ErrorKind::NotFound, "missing").into();
+        assert_eq!("I/O error: missing", e.to_string());
+        assert!(e.source().is_some());
+    }
+}
@@ -0,0 +1,289 @@
+//! Exact rational arithmetic in a 2-Dimensional vector space.
+//!
+//! Provides an exact kernel for the computational geometry library [cg_library](crate), which is
+//! only available with the cargo feature `exact`. Every finite `f64` is a rational number, so all
+//! coordinates are converted without loss and intersections as well as areas are calculated with
+//! arbitrary-precision rationals. Only the results are converted back to [Point2D]. Infinite and
+//! NaN coordinates are no rationals, they are reported as a [CgError].
+
+use crate::error::CgError;
+use crate::linesegment2d::LineSegment2D;
+use crate::point2d::Point2D;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A point in a 2-Dimensional vector space with rational coordinates.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::exact::ExactPoint2D;
+/// use cg_library::point2d::Point2D;
+/// let p = Point2D { x: 0.1, y: 2.0 };
+/// assert_eq!(p, ExactPoint2D::from_point(&p).unwrap().to_point());
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct ExactPoint2D {
+    /// The x-coordinate.
+    pub x: BigRational,
+    /// The y-coordinate.
+    pub y: BigRational,
+}
+
+/// The result of intersecting two line segments exactly, see
+/// [SegmentIntersection](crate::linesegment2d::SegmentIntersection).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ExactIntersection {
+    /// The segments share no point.
+    None,
+
+    /// The segments cross or touch in exactly one point.
+    Point(ExactPoint2D),
+
+    /// The segments are colinear and overlap from the first to the second point.
+    Overlap(ExactPoint2D, ExactPoint2D),
+}
+
+impl ExactPoint2D {
+    /// Returns the exact point of a point, or an error if a coordinate is not finite.
+    pub fn from_point(p: &Point2D) -> Result<ExactPoint2D, CgError> {
+        if p.is_nan() {
+            return Err(CgError::NanCoordinate);
+        }
+        let convert =
+            |value: f64| BigRational::from_float(value).ok_or(CgError::InfiniteCoordinate);
+        Ok(ExactPoint2D {
+            x: convert(p.x)?,
+            y: convert(p.y)?,
+        })
+    }
+
+    /// Returns the point with the nearest `f64` coordinates.
+    pub fn to_point(&self) -> Point2D {
+        Point2D {
+            x: self.x.to_f64().unwrap(),
+            y: self.y.to_f64().unwrap(),
+        }
+    }
+}
+
+/// This trait allows a point to be displayed in the form of `(x,y)` with fractions.
+impl fmt::Display for ExactPoint2D {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
+    }
+}
+
+/// Returns the exact counter clock wise value for three points, see [ccw](crate::tools2d::ccw).
+pub fn ccw(p: &ExactPoint2D, q: &ExactPoint2D, r: &ExactPoint2D) -> BigRational {
+    (&q.x - &p.x) * (&r.y - &p.y) - (&q.y - &p.y) * (&r.x - &p.x)
+}
+
+/// Returns the exact endpoints of a segment.
+///
+/// # Panics
+/// This function panics if a coordinate is not finite, the
+/// [sweep line](crate::util::sweepline::SweepLine) rejects such segments on insertion.
+pub(crate) fn endpoints(s: &LineSegment2D) -> (ExactPoint2D, ExactPoint2D) {
+    let convert = |p: &Point2D| ExactPoint2D::from_point(p).unwrap_or_else(|e| panic!("{e}"));
+    (convert(&s.p1), convert(&s.p2))
+}
+
+/// Returns the exact y-coordinate of the line through a non vertical segment at an x-coordinate.
+pub(crate) fn y_at(s: &LineSegment2D, x: &BigRational) -> BigRational {
+    let (p1, p2) = endpoints(s);
+    &p1.y + (&p2.y - &p1.y) * (x - &p1.x) / (&p2.x - &p1.x)
+}
+
+/// Returns the exact order of the slopes of two non vertical segments.
+pub(crate) fn compare_slopes(a: &LineSegment2D, b: &LineSegment2D) -> Ordering {
+    let ((a1, a2), (b1, b2)) = (endpoints(a), endpoints(b));
+    // Both segments run from left to right, so the denominators of the slopes are positive
+    ((&a2.y - &a1.y) * (&b2.x - &b1.x)).cmp(&((&b2.y - &b1.y) * (&a2.x - &a1.x)))
+}
+
+/// Calculates the exact intersection of two line segments.
+///
+/// This is the exact counterpart of [intersects](LineSegment2D::intersects), the intersection
+/// point is not rounded. It returns an error if a coordinate is not finite.
+pub fn intersection(
+    s: &LineSegment2D,
+    other: &LineSegment2D,
+) -> Result<ExactIntersection, CgError> {
+    let (p1, p2) = (
+        ExactPoint2D::from_point(&s.p1)?,
+        ExactPoint2D::from_point(&s.p2)?,
+    );
+    let (q1, q2) = (
+        ExactPoint2D::from_point(&other.p1)?,
+        ExactPoint2D::from_point(&other.p2)?,
+    );
+    Ok(exact_intersection(p1, p2, q1, q2))
+}
+
+/// Returns the exact intersection of the segment from `p1` to `p2` with the one from `q1` to
+/// `q2`, the endpoints of both segments are sorted.
+fn exact_intersection(
+    p1: ExactPoint2D,
+    p2: ExactPoint2D,
+    q1: ExactPoint2D,
+    q2: ExactPoint2D,
+) -> ExactIntersection {
+    // A degenerate segment only intersects where its point lies on the other segment
+    let on = |p: &ExactPoint2D, a: &ExactPoint2D, b: &ExactPoint2D| {
+        ccw(a, b, p).is_zero() && a <= p && p <= b
+    };
+    if p1 == p2 || q1 == q2 {
+        let (p, a, b) = if p1 == p2 { (p1, q1, q2) } else { (q1, p1, p2) };
+        if on(&p, &a, &b) {
+            return ExactIntersection::Point(p);
+        }
+        return ExactIntersection::None;
+    }
+
+    let (o1, o2) = (ccw(&p1, &p2, &q1), ccw(&p1, &p2, &q2));
+    if o1.is_zero() && o2.is_zero() {
+        // Both segments are sorted, so the overlap is spanned by the later start point and the
+        // earlier end point
+        let start = p1.max(q1);
+        let end = p2.min(q2);
+        return match start.cmp(&end) {
+            Ordering::Less => ExactIntersection::Overlap(start, end),
+            Ordering::Equal => ExactIntersection::Point(start),
+            Ordering::Greater => ExactIntersection::None,
+        };
+    }
+
+    let (o3, o4) = (ccw(&q1, &q2, &p1), ccw(&q1, &q2, &p2));
+    if o1.signum() * o2.signum() > BigRational::zero()
+        || o3.signum() * o4.signum() > BigRational::zero()
+    {
+        return ExactIntersection::None;
+    }
+
+    // The point divides the segment s in the ratio of the distances of its endpoints to the
+    // other segment
+    let t = &o3 / (&o3 - &o4);
+    ExactIntersection::Point(ExactPoint2D {
+        x: &p1.x + &t * (&p2.x - &p1.x),
+        y: &p1.y + &t * (&p2.y - &p1.y),
+    })
+}
+
+/// Returns the exact area of a closed ring of points, see
+/// [signed_area](crate::polygon2d::Polygon2D::signed_area), or an error if a coordinate is not
+/// finite.
+pub fn area(points: &[Point2D]) -> Result<BigRational, CgError> {
+    let origin = ExactPoint2D::from_point(&Point2D::new())?;
+    let points: Vec<ExactPoint2D> = points
+        .iter()
+        .map(ExactPoint2D::from_point)
+        .collect::<Result<_, _>>()?;
+    let area: BigRational = points.windows(2).map(|w| ccw(&origin, &w[0], &w[1])).sum();
+    Ok(area / BigRational::from_integer(2.into()))
+}
+
+#[cfg(test)]
+mod test_exact {
+    use super::*;
+    use crate::tools2d::{bently_ottmann_exact, read_segments_from_file};
+
+    #[test]
+    fn test_intersection() {
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 3.0, y: 1.0 });
+        let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 1.0 }, Point2D { x: 3.0, y: 0.0 });
+        let expected = ExactPoint2D {
+            x: BigRational::new(3.into(), 2.into()),
+            y: BigRational::new(1.into(), 2.into()),
+        };
+        assert_eq!(
+            ExactIntersection::Point(expected),
+            intersection(&s1, &s2).unwrap()
+        );
+
+        // The intersection at (1/3,1/3) is not representable as f64
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 1.0, y: 1.0 });
+        let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 1.0 }, Point2D { x: 1.0, y: -1.0 });
+        let third = BigRational::new(1.into(), 3.into());
+        let expected = ExactPoint2D {
+            x: third.clone(),
+            y: third,
+        };
+        assert_eq!(
+            ExactIntersection::Point(expected),
+            intersection(&s1, &s2).unwrap()
+        );
+
+        // s1 and s2 are colinear and overlap from (1.5,1.5) to (2,2)
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let s2 = LineSegment2D::new(Point2D { x: 3.0, y: 3.0 }, Point2D { x: 1.5, y: 1.5 });
+        assert_eq!(
+            ExactIntersection::Overlap(
+                ExactPoint2D::from_point(&Point2D { x: 1.5, y: 1.5 }).unwrap(),
+                ExactPoint2D::from_point(&Point2D { x: 2.0, y: 2.0 }).unwrap()
+            ),
+            intersection(&s1, &s2).unwrap()
+        );
+
+        // s1 and s2 share no point
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let s2 = LineSegment2D::new(Point2D { x: -2.0, y: 1.0 }, Point2D { x: 1.0, y: -2.0 });
+        assert_eq!(ExactIntersection::None, intersection(&s1, &s2).unwrap());
+
+        // s2 is a single point on s1, s3 a single point on the line of s1 only
+        let s2 = LineSegment2D::new(Point2D { x: 0.5, y: 0.5 }, Point2D { x: 0.5, y: 0.5 });
+        let s3 = LineSegment2D::new(Point2D { x: 3.0, y: 3.0 }, Point2D { x: 3.0, y: 3.0 });
+        assert_eq!(
+            ExactIntersection::Point(ExactPoint2D::from_point(&s2.p1).unwrap()),
+            intersection(&s2, &s1).unwrap()
+        );
+        assert_eq!(ExactIntersection::None, intersection(&s1, &s3).unwrap());
+    }
+
+    #[test]
+    fn test_area() {
+        let points = vec![
+            Point2D { x: 0.1, y: 0.1 },
+            Point2D { x: 0.1, y: 0.2 },
+            Point2D { x: 0.2, y: 0.2 },
+            Point2D { x: 0.1, y: 0.1 },
+        ];
+        // The legs are not exactly 0.1 long, since 0.1 and 0.2 are rounded
+        let leg = BigRational::from_float(0.2).unwrap() - BigRational::from_float(0.1).unwrap();
+        let expected = -(&leg * &leg) / BigRational::from_integer(2.into());
+        assert_eq!(expected, area(&points).unwrap());
+    }
+
+    #[test]
+    fn test_bently_ottmann() {
+        let segments = read_segments_from_file("../data/s_1000_10.dat");
+        let intersections = bently_ottmann_exact(segments).unwrap();
+        assert_eq!(796, intersections.len());
+    }
+
+    #[test]
+    fn test_infinite() {
+        let p = Point2D {
+            x: f64::INFINITY,
+            y: 1.0,
+        };
+        assert!(matches!(
+            ExactPoint2D::from_point(&p),
+            Err(CgError::InfiniteCoordinate)
+        ));
+        assert!(matches!(
+            ExactPoint2D::from_point(&Point2D {
+                x: 1.0,
+                y: f64::NAN
+            }),
+            Err(CgError::NanCoordinate)
+        ));
+
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, p);
+        let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 2.0, y: 0.0 });
+        assert!(intersection(&s1, &s2).is_err());
+        assert!(bently_ottmann_exact(vec![s1, s2]).is_err());
+    }
+}
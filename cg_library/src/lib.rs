@@ -3,26 +3,62 @@
 //! Provides datatypes and tools for calculating in the field of geometry. The peak of this
 //! library is the implementation of the `sweep-line` or [bently
 //! ottmann](tools2d::bently_ottmann) algorithm to calculate a set of intersection points of
-//! $N$ line segments. The underlying structure provides following datatypes:
-//! - [Point2D](point2d::Point2D)
-//! - [Line2D](line2d::Line2D)
-//! - [LineSegment2D](linesegment2d::LineSegment2D)
-//! - [Polygon2D](polygon2d::Polygon2D)
+//! $N$ line segments. The underlying structure provides following datatypes, which are generic
+//! over a [scalar](scalar::Scalar) and have an alias for `f64` coordinates:
+//! - [Point](point2d::Point) and [Point2D](point2d::Point2D)
+//! - [Line](line2d::Line) and [Line2D](line2d::Line2D)
+//! - [LineSegment](linesegment2d::LineSegment) and [LineSegment2D](linesegment2d::LineSegment2D)
+//! - [Polygon](polygon2d::Polygon) and [Polygon2D](polygon2d::Polygon2D)
+//! - [Aabb](aabb2d::Aabb) and [Aabb2D](aabb2d::Aabb2D), the bounding box of all of these
+//! - [Vector](vector2d::Vector) and [Vector2D](vector2d::Vector2D)
+//! - [Transform](transform2d::Transform) and [Transform2D](transform2d::Transform2D), which map
+//!   all of the above
+//!
+//! The algorithms of the sweep line work on `f64` coordinates:
 //! - [EventPoint](util::eventpoint::EventPoint)
+//! - [Intersection](util::intersection::Intersection)
 //! - [SweepLine](util::sweepline::SweepLine)
+//! - [YStructure](util::ystructure::YStructure)
+//!
+//! Their floating point comparisons and the snapping of intersection points follow a configurable
+//! [Tolerance](tolerance::Tolerance).
+//!
+//! Distances and closest points between the primitives are queried with the [distance2d]
+//! functions.
+//!
+//! Invalid input, like unreadable files or NaN coordinates, is reported by the `try_` constructors
+//! and readers as a [CgError](error::CgError) instead of a panic.
+//! Invalid polygon rings, like self-intersecting borders of maps, are split into simple polygons
+//! and holes by the [repair](repair2d) with a [report](repair2d::RepairReport) of every change.
+//!
+//! With the cargo feature `exact` the [exact kernel](exact) module provides rational arithmetic for
+//! intersections and areas. The cargo feature `serde` implements serialization for all types, the
+//! derived parts of segments and polygons are rebuilt on deserialization.
 //!
 //! The library was created in order to fulfill all requirements for the course `computational
 //! geometry` in the first master semester.
 
 #![allow(dead_code)]
 
+pub mod aabb2d;
+pub mod distance2d;
+pub mod error;
+#[cfg(feature = "exact")]
+pub mod exact;
 pub mod line2d;
 pub mod linesegment2d;
 pub mod point2d;
 pub mod polygon2d;
+pub mod repair2d;
+pub mod scalar;
+pub mod tolerance;
 pub mod tools2d;
+pub mod transform2d;
+pub mod vector2d;
 pub mod util {
     //! This section provides more advanced datatypes.
     pub mod eventpoint;
+    pub mod intersection;
     pub mod sweepline;
+    pub mod ystructure;
 }
@@ -2,132 +2,278 @@
 //!
 //! Provides a line struct for the computational geometry library [cg_library](crate).
 
-use crate::point2d::Point2D;
+use crate::error::CgError;
+use crate::point2d::Point;
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+use crate::tools2d::parse_value;
+use crate::vector2d::Vector;
+use num_traits::Float;
+use std::cmp::Ordering;
 use std::fmt;
+use std::str::FromStr;
 
 /// A line in a 2D vector space.
 ///
-/// A line consists of a y-intercept as well as a slope.
+/// A line consists of the coefficients of its implicit equation `a * x + b * y + c = 0`, which
+/// handles lines of every orientation uniformly. These coefficients are the homogeneous
+/// coordinates of the line, so they can be scaled by any factor other than zero without changing
+/// the line. Its coefficients are of any [scalar](crate::scalar::Scalar) type, [Line2D] is the
+/// line with `f64` coefficients.
+///
+/// The normal `(a,b)` points to the left of the direction `(b,-a)` of the line. For a line
+/// [from two points](Line::from_point_and_point) the direction runs from the first to the
+/// second point.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::line2d::Line;
+/// let l: Line<i64> = Line { a: 1, b: -1, c: 0 };
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line<T> {
+    /// The coefficient `a` of the x-coordinate.
+    pub a: T,
+    /// The coefficient `b` of the y-coordinate.
+    pub b: T,
+    /// The constant coefficient `c`.
+    pub c: T,
+}
+
+/// A line in a 2D vector space with `f64` coefficients.
 ///
 /// # Example
 ///
 /// ```
 /// use cg_library::line2d::Line2D;
-/// let l: Line2D = Line2D { slope: 1.0, intercept: 0.0};
+/// let l: Line2D = Line2D { a: 1.0, b: -1.0, c: 0.0 };
 /// ```
+pub type Line2D = Line<f64>;
+
+/// This trait allows the comparison of two lines, it returns true if they are the same line.
 ///
-/// If the line is parallel to the y-achsis, the slope is set to be `std::f64::INFINITY` and the
-/// intercept will contain the x-coordinate information of that line.
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
-pub struct Line2D {
-    /// The slope `m` of a line `f(x) -> m * x + t`.
-    pub slope: f64,
-    /// The intercept `t` of a line `f(x) -> m * x + t`.
-    pub intercept: f64,
+/// The coefficients of equal lines only need to be proportional. The [zero](Line::is_zero) line
+/// is proportional to every line, so it is only equal to itself.
+impl<T: Scalar> PartialEq for Line<T> {
+    fn eq(&self, other: &Line<T>) -> bool {
+        self.is_zero() == other.is_zero()
+            && self.a * other.b == other.a * self.b
+            && self.a * other.c == other.a * self.c
+            && self.b * other.c == other.b * self.c
+    }
 }
 
-/// This trait needs to be implemented to satisfy PartialOrd, it is not yet used.
-impl Eq for Line2D {}
+/// This trait is implemented, since the comparison of lines is an equivalence relation.
+impl<T: Scalar> Eq for Line<T> {}
 
-impl Line2D {
-    /// Returns `true` iff the line is vertical (slope is infinite).
-    pub fn is_vertical(self) -> bool {
-        self.slope.is_infinite()
+impl<T: Scalar> Line<T> {
+    /// Returns a new instance from two points on the line.
+    ///
+    /// The coefficients are calculated with a cross product of the homogeneous coordinates of the
+    /// points, so [side_of](Line::side_of) has the sign of [ccw](crate::tools2d::ccw).
+    pub fn from_point_and_point(p1: Point<T>, p2: Point<T>) -> Line<T> {
+        Line {
+            a: p1.y - p2.y,
+            b: p2.x - p1.x,
+            c: p1.x * p2.y - p2.x * p1.y,
+        }
     }
 
-    /// Returns `true` iff the line is horizontal (slope is zero).
-    pub fn is_horizontal(self) -> bool {
-        self.slope == 0.0
+    /// Returns `true` iff all coefficients are zero, like in the line of a
+    /// [degenerate](crate::linesegment2d::LineSegment::is_degenerate) segment.
+    ///
+    /// Every point satisfies the equation of the zero line, it has neither a normal nor a
+    /// direction.
+    pub fn is_zero(self) -> bool {
+        self.a == T::zero() && self.b == T::zero() && self.c == T::zero()
     }
 
-    /// Returns a new instance from slope and one point on the line.
-    pub fn from_slope_and_point(slope: f64, p: Point2D) -> Line2D {
-        let intercept;
-        if slope.is_infinite() {
-            intercept = p.x;
-        } else {
-            intercept = p.y - slope * p.x;
+    /// Returns the normal `(a,b)` of the line, which points to its left side.
+    pub fn normal(self) -> Vector<T> {
+        Vector {
+            x: self.a,
+            y: self.b,
         }
-        return Line2D { slope, intercept };
     }
 
-    /// Returns a new instance from two points on the line.
-    pub fn from_point_and_point(p1: Point2D, p2: Point2D) -> Line2D {
-        let slope;
-        let intercept;
-        if p1.x == p2.x {
-            slope = std::f64::INFINITY;
-            intercept = p1.x;
-        } else {
-            slope = (p2.y - p1.y) / (p2.x - p1.x);
-            intercept = p2.y - slope * p2.x;
+    /// Returns the direction `(b,-a)` of the line.
+    pub fn direction(self) -> Vector<T> {
+        -self.normal().perpendicular()
+    }
+
+    /// Returns `true` iff the line is vertical.
+    pub fn is_vertical(self) -> bool {
+        self.b == T::zero()
+    }
+
+    /// Returns `true` iff the line is horizontal.
+    pub fn is_horizontal(self) -> bool {
+        self.a == T::zero()
+    }
+
+    /// Returns the slope of the line, or `None` if the line is vertical.
+    pub fn slope(self) -> Option<T> {
+        if self.is_vertical() {
+            return None;
         }
-        return Line2D { slope, intercept };
+        Some(-self.a / self.b)
     }
 
     /// Returns the y-coordinate from a given x-coordinate on the line.
     ///
-    /// If the line is vertical, the x-coordinate is returned.
-    pub fn y_from_x(self, x: f64) -> f64 {
+    /// If the line is vertical, there is no single y-coordinate and `None` is returned.
+    pub fn y_from_x(self, x: T) -> Option<T> {
         if self.is_vertical() {
-            return self.intercept;
+            return None;
         }
-        return self.slope * x + self.intercept;
+        Some(-(self.a * x + self.c) / self.b)
     }
 
-    /// Returns `true` iff a point is lies ontop of the line.
-    pub fn contains(self, p: &Point2D) -> bool {
-        if self.is_vertical() {
-            return self.intercept == p.x;
-        } else {
-            return self.slope * p.x + self.intercept == p.y;
+    /// Returns the x-coordinate from a given y-coordinate on the line.
+    ///
+    /// If the line is horizontal, there is no single x-coordinate and `None` is returned.
+    pub fn x_from_y(self, y: T) -> Option<T> {
+        if self.is_horizontal() {
+            return None;
         }
+        Some(-(self.b * y + self.c) / self.a)
+    }
+
+    /// Returns the value of the equation of the line at a point.
+    ///
+    /// The value is zero on the line and its sign tells the side of the point, see
+    /// [side_of](Line::side_of). Divided by the length of the normal it is the signed distance.
+    pub fn evaluate(self, p: &Point<T>) -> T {
+        self.a * p.x + self.b * p.y + self.c
+    }
+
+    /// Returns the side of the line a point lies on.
+    ///
+    /// The result is `Greater` if the point lies left of the direction of the line, `Less` if it
+    /// lies right of it and `Equal` if it lies on the line. The value is compared
+    /// [canonically](Scalar::canonical_cmp), so a point with a NaN coordinate is `Greater` instead
+    /// of a panic.
+    pub fn side_of(self, p: &Point<T>) -> Ordering {
+        self.evaluate(p).canonical_cmp(&T::zero())
+    }
+
+    /// Returns `true` iff a point is lies ontop of the line.
+    pub fn contains(self, p: &Point<T>) -> bool {
+        self.side_of(p) == Ordering::Equal
     }
 
     /// Returns `true` iff line is parallel to a given other line.
-    pub fn is_parallel_to(self, other: &Line2D) -> bool {
-        self.slope == other.slope
+    ///
+    /// Two lines are parallel iff the cross product of their normals is zero, equal lines are
+    /// parallel as well.
+    pub fn is_parallel_to(self, other: &Line<T>) -> bool {
+        self.a * other.b == other.a * self.b
     }
 
     /// Returns a intersection point of two lines, when the lines are not parallel.
-    pub fn intersection(self, other: &Line2D) -> Option<Point2D> {
-        if self.is_parallel_to(other) {
+    ///
+    /// The homogeneous coordinates of the point are the cross product of the coefficients of
+    /// both lines.
+    pub fn intersection(self, other: &Line<T>) -> Option<Point<T>> {
+        let w = self.a * other.b - other.a * self.b;
+        if w == T::zero() {
             return None;
         }
 
-        let (x, y) = if self.is_vertical() {
-            (
-                self.intercept,
-                other.slope * self.intercept + other.intercept,
-            )
-        } else if other.is_vertical() {
-            (
-                other.intercept,
-                self.slope * other.intercept + self.intercept,
-            )
-        } else {
-            let x = (self.intercept - other.intercept) / (other.slope - self.slope);
-            let y = self.slope * x + self.intercept;
-            (x, y)
-        };
+        Some(Point {
+            x: (self.b * other.c - other.b * self.c) / w,
+            y: (self.c * other.a - other.c * self.a) / w,
+        })
+    }
+}
+
+impl<T: Scalar + Float> Line<T> {
+    /// Returns a new instance from slope and one point on the line.
+    ///
+    /// An infinite slope results in a vertical line through the point.
+    pub fn from_slope_and_point(slope: T, p: Point<T>) -> Line<T> {
+        if slope.is_infinite() {
+            return Line {
+                a: T::one(),
+                b: T::zero(),
+                c: -p.x,
+            };
+        }
+        Line {
+            a: -slope,
+            b: T::one(),
+            c: slope * p.x - p.y,
+        }
+    }
 
-        Some(Point2D { x, y })
+    /// Returns the same line with a normal of length one.
+    ///
+    /// The value of the equation at a point is then its signed distance to the line.
+    pub fn normalized(self) -> Line<T> {
+        let length = self.a.hypot(self.b);
+        Line {
+            a: self.a / length,
+            b: self.b / length,
+            c: self.c / length,
+        }
+    }
+
+    /// Returns the euclidean distance of a point to the line.
+    pub fn distance_to(self, p: &Point<T>) -> T {
+        self.evaluate(p).abs() / self.a.hypot(self.b)
+    }
+
+    /// Returns `true` iff the distance of a point to the line lies within the tolerance.
+    ///
+    /// Unlike [contains](Line::contains) this accepts points that miss the line only because of
+    /// rounded coordinates.
+    pub fn contains_within(self, p: &Point<T>, tolerance: &Tolerance) -> bool {
+        self.distance_to(p) <= tolerance.bound(p.x.abs().max(p.y.abs()))
+    }
+
+    /// Returns `true` iff the sine of the angle between both lines is zero within the absolute
+    /// epsilon of the tolerance.
+    pub fn is_parallel_to_within(self, other: &Line<T>, tolerance: &Tolerance) -> bool {
+        let sine =
+            self.normal().cross(other.normal()) / (self.a.hypot(self.b) * other.a.hypot(other.b));
+        tolerance.is_zero(sine)
     }
 }
 
-/// This trait allows a line to be displayed in the form of `f(x) -> {slope} * x + {intercept}`.
-///
-/// If the line is vertical it indicates this with `f(y) -> {x}`.
-impl fmt::Display for Line2D {
+/// This trait allows a line to be displayed in the form of `{a} * x {b} * y {c} = 0`.
+impl<T: Scalar> fmt::Display for Line<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if self.is_vertical() {
-            write!(f, "f(y) -> {:+}", self.intercept)
-        } else {
-            if self.is_horizontal() {
-                write!(f, "f(x) -> {:+}", self.intercept)
-            } else {
-                write!(f, "f(x) -> {:+} * x {:+}", self.slope, self.intercept)
-            }
+        write!(f, "{} * x {:+} * y {:+} = 0", self.a, self.b, self.c)
+    }
+}
+
+/// This trait allows a line to be parsed from its displayed form `a * x +b * y +c = 0`.
+///
+/// The signs of `b` and `c` are optional and any whitespace separates the terms.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::line2d::Line2D;
+/// let l: Line2D = "1 * x -2 * y +0.5 = 0".parse().unwrap();
+/// assert_eq!((1.0, -2.0, 0.5), (l.a, l.b, l.c));
+/// ```
+impl<T: Scalar + FromStr> FromStr for Line<T> {
+    type Err = CgError;
+
+    fn from_str(s: &str) -> Result<Line<T>, CgError> {
+        let terms: Vec<&str> = s.split_whitespace().collect();
+        match terms[..] {
+            [a, "*", "x", b, "*", "y", c, "=", "0"] => Ok(Line {
+                a: parse_value(a)?,
+                b: parse_value(b)?,
+                c: parse_value(c)?,
+            }),
+            _ => Err(CgError::Format(format!(
+                "{s:?} is no line of the form a * x +b * y +c = 0"
+            ))),
         }
     }
 }
@@ -135,33 +281,51 @@ impl fmt::Display for Line2D {
 #[cfg(test)]
 mod test_line2d {
     use super::*;
+    use crate::point2d::Point2D;
 
     #[test]
     fn test_vertical() {
-        let l1 = Line2D {
-            slope: std::f64::INFINITY,
-            intercept: 10.0,
-        };
+        let l1 =
+            Line2D::from_point_and_point(Point2D { x: 10.0, y: 0.0 }, Point2D { x: 10.0, y: 1.0 });
         assert!(l1.is_vertical());
+        assert!(!l1.is_horizontal());
+        assert_eq!(None, l1.slope());
+        assert_eq!(None, l1.y_from_x(10.0));
+        assert_eq!(Some(10.0), l1.x_from_y(4.0));
+        assert!(l1.contains(&Point2D { x: 10.0, y: -3.0 }));
     }
 
     #[test]
     fn test_display() {
         let l1 = Line2D {
-            slope: std::f64::INFINITY,
-            intercept: 10.0,
+            a: 1.0,
+            b: 0.0,
+            c: -10.0,
         };
         let l2 = Line2D {
-            slope: -12.0,
-            intercept: -4.0,
+            a: 12.0,
+            b: 1.0,
+            c: 4.0,
         };
-        let l3 = Line2D {
-            slope: 0.0,
-            intercept: -4.0,
+        assert_eq!("1 * x +0 * y -10 = 0", l1.to_string());
+        assert_eq!("12 * x +1 * y +4 = 0", l2.to_string());
+    }
+
+    #[test]
+    fn test_parse() {
+        let l = Line2D {
+            a: 0.1 + 0.2,
+            b: -1e-300,
+            c: -0.0,
         };
-        assert_eq!("f(y) -> +10", l1.to_string());
-        assert_eq!("f(x) -> -12 * x -4", l2.to_string());
-        assert_eq!("f(x) -> -4", l3.to_string());
+        let parsed: Line2D = l.to_string().parse().unwrap();
+        assert_eq!((l.a, l.b, l.c), (parsed.a, parsed.b, parsed.c));
+
+        let l: Line<i64> = "3 * x  -1 * y 4 = 0".parse().unwrap();
+        assert_eq!((3, -1, 4), (l.a, l.b, l.c));
+
+        assert!("1 * x +2 * y = 0".parse::<Line2D>().is_err());
+        assert!("1 * x +a * y +1 = 0".parse::<Line2D>().is_err());
     }
 
     #[test]
@@ -169,26 +333,115 @@ mod test_line2d {
         let l1: Line2D = Line2D::from_slope_and_point(1.0, Point2D { x: 1.0, y: 1.0 });
         let l2: Line2D =
             Line2D::from_point_and_point(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 2.0, y: 0.0 });
-        assert_eq!("f(x) -> +1 * x +0", l1.to_string());
-        assert_eq!("f(x) -> -1 * x +2", l2.to_string());
+        let l3: Line2D = Line2D::from_slope_and_point(f64::INFINITY, Point2D { x: 1.0, y: 1.0 });
+        assert_eq!("-1 * x +1 * y +0 = 0", l1.to_string());
+        assert_eq!("2 * x +2 * y -4 = 0", l2.to_string());
+        assert_eq!(Some(1.0), l1.slope());
+        assert_eq!(Some(-1.0), l2.slope());
+        assert_eq!(Some(2.0), l2.y_from_x(0.0));
+        assert!(l3.is_vertical());
+
+        // Scaled coefficients are the same line
+        assert_eq!(
+            Line2D {
+                a: 1.0,
+                b: 1.0,
+                c: -2.0
+            },
+            l2
+        );
+        assert_ne!(l1, l2);
     }
 
     #[test]
-    fn test_intersection() {
-        let l1: Line2D = Line2D {
-            slope: std::f64::INFINITY,
-            intercept: 1.0,
-        };
-        let l2: Line2D = Line2D {
-            slope: std::f64::INFINITY,
-            intercept: 2.0,
+    fn test_zero() {
+        let zero: Line2D =
+            Line2D::from_point_and_point(Point2D { x: 1.0, y: 2.0 }, Point2D { x: 1.0, y: 2.0 });
+        let l1: Line2D = Line2D::from_slope_and_point(1.0, Point2D { x: 0.0, y: 0.0 });
+        let l2: Line2D = Line2D::from_slope_and_point(-1.0, Point2D { x: 0.0, y: 0.0 });
+        assert!(zero.is_zero());
+        assert!(!l1.is_zero());
+
+        // The zero line is proportional to both lines, but equal to none of them
+        assert_eq!(
+            zero,
+            Line2D {
+                a: 0.0,
+                b: -0.0,
+                c: 0.0
+            }
+        );
+        assert_ne!(zero, l1);
+        assert_ne!(l2, zero);
+        assert_ne!(l1, l2);
+    }
+
+    #[test]
+    fn test_side() {
+        let l1: Line<i64> = Line::from_point_and_point(Point { x: 0, y: 0 }, Point { x: 4, y: 2 });
+        assert_eq!(Ordering::Greater, l1.side_of(&Point { x: 0, y: 1 }));
+        assert_eq!(Ordering::Less, l1.side_of(&Point { x: 1, y: 0 }));
+        assert_eq!(Ordering::Equal, l1.side_of(&Point { x: -2, y: -1 }));
+        assert!(l1.contains(&Point { x: 2, y: 1 }));
+        assert_eq!(Vector { x: 4, y: 2 }, l1.direction());
+        assert_eq!(Vector { x: -2, y: 4 }, l1.normal());
+
+        let l2: Line2D =
+            Line2D::from_point_and_point(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 2.0 });
+        let nan = Point2D {
+            x: f64::NAN,
+            y: 1.0,
         };
-        let l3: Line2D = Line2D {
-            slope: 4.0,
-            intercept: 1.0,
+        assert_eq!(Ordering::Greater, l2.side_of(&nan));
+    }
+
+    #[test]
+    fn test_distance() {
+        let l1: Line2D =
+            Line2D::from_point_and_point(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 3.0, y: 4.0 });
+        assert_eq!(5.0, l1.distance_to(&Point2D { x: 4.0, y: -3.0 }));
+        assert_eq!(0.0, l1.distance_to(&Point2D { x: 6.0, y: 8.0 }));
+        assert_eq!(-5.0, l1.normalized().evaluate(&Point2D { x: 4.0, y: -3.0 }));
+
+        let l2: Line2D =
+            Line2D::from_point_and_point(Point2D { x: 2.0, y: 0.0 }, Point2D { x: 2.0, y: 1.0 });
+        assert_eq!(2.0, l2.distance_to(&Point2D { x: 0.0, y: 7.0 }));
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let l1: Line2D =
+            Line2D::from_point_and_point(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 0.3, y: 0.1 });
+        let l2: Line2D =
+            Line2D::from_point_and_point(Point2D { x: 0.0, y: 1.0 }, Point2D { x: 3.0, y: 2.0 });
+        let p = Point2D {
+            x: 0.6,
+            y: 0.2 + 1e-12,
         };
+        assert!(!l1.contains(&p));
+        assert!(l1.contains_within(&p, &Tolerance::default()));
+        assert!(!l1.contains_within(&p, &Tolerance::exact()));
+        assert!(!l1.is_parallel_to(&l2));
+        assert!(l1.is_parallel_to_within(&l2, &Tolerance::default()));
+    }
+
+    #[test]
+    fn test_intersection() {
+        let l1: Line2D = Line2D::from_slope_and_point(f64::INFINITY, Point2D { x: 1.0, y: 0.0 });
+        let l2: Line2D = Line2D::from_slope_and_point(f64::INFINITY, Point2D { x: 2.0, y: 0.0 });
+        let l3: Line2D = Line2D::from_slope_and_point(4.0, Point2D { x: 0.0, y: 1.0 });
+        assert!(l1.is_parallel_to(&l2));
         assert_eq!(None, l1.intersection(&l2));
         assert_eq!(Some(Point2D { x: 1.0, y: 5.0 }), l1.intersection(&l3));
         assert_eq!(Some(Point2D { x: 1.0, y: 5.0 }), l3.intersection(&l1));
+
+        let l4: Line<f32> =
+            Line::from_point_and_point(Point { x: 0.0, y: 2.0 }, Point { x: 2.0, y: 0.0 });
+        let l5: Line<f32> = Line::from_slope_and_point(1.0, Point { x: 0.0, y: 0.0 });
+        assert_eq!(Some(Point { x: 1.0, y: 1.0 }), l4.intersection(&l5));
+
+        let l6: Line<i64> = Line::from_point_and_point(Point { x: 0, y: 0 }, Point { x: 4, y: 4 });
+        let l7: Line<i64> = Line::from_point_and_point(Point { x: 0, y: 4 }, Point { x: 4, y: 0 });
+        assert_eq!(Some(Point { x: 2, y: 2 }), l6.intersection(&l7));
     }
 }
@@ -2,18 +2,40 @@
 //!
 //! Provides a linesegment struct for the computational geometry library [cg_library](crate).
 
-use crate::line2d::Line2D;
-use crate::point2d::Point2D;
-use crate::tools2d::ccw;
+use crate::aabb2d::Aabb;
+use crate::distance2d::point_segment;
+use crate::error::CgError;
+use crate::line2d::Line;
+use crate::point2d::Point;
+use crate::scalar::{Field, Scalar};
+use crate::tolerance::Tolerance;
+use crate::tools2d::parse_value;
+use crate::vector2d::Vector;
+use num_traits::Float;
 use std::cmp::Ordering;
 use std::fmt;
-
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        from = "SegmentPoints<T>",
+        into = "SegmentPoints<T>",
+        bound(
+            serialize = "T: Scalar + serde::Serialize",
+            deserialize = "T: Scalar + serde::Deserialize<'de>"
+        )
+    )
+)]
 /// A line segment in a 2D vector space.
 ///
 /// A line segment consists of two points, that are the endpoints of the segment. It is used in
 /// combination with the bently_ottmann algorithm as well as a
-/// [Polygon2D](crate::polygon2d::Polygon2D).
+/// [Polygon](crate::polygon2d::Polygon). Its coordinates are of any
+/// [scalar](crate::scalar::Scalar) type, [LineSegment2D] is the segment with `f64` coordinates.
 ///
 /// # Example
 ///
@@ -24,123 +46,295 @@ use std::fmt;
 /// let p2: Point2D = Point2D{x: 1.0, y: 1.0};
 /// let ls: LineSegment2D = LineSegment2D::new(p1, p2);
 /// ```
-pub struct LineSegment2D {
-    /// The line that runs through this Segment
-    pub line: Line2D,
-
-    /// The [point](crate::point2d::Point2D) that has the smallest x-coordinate, or if equal, the smallest y-coordinate
-    pub p1: Point2D,
+pub struct LineSegment<T> {
+    /// The [point](crate::point2d::Point) that has the smallest x-coordinate, or if equal, the smallest y-coordinate
+    pub p1: Point<T>,
 
-    /// The [point](crate::point2d::Point2D) that has the highest x-coordinate, or if equal, the highest y-coordinate
-    pub p2: Point2D,
+    /// The [point](crate::point2d::Point) that has the highest x-coordinate, or if equal, the highest y-coordinate
+    pub p2: Point<T>,
 
     /// Maximum x-coordinate of the bounding box of the segment
-    pub max_x: f64,
+    pub max_x: T,
 
     /// Maximum y-coordinate of the bounding box of the segment
-    pub max_y: f64,
+    pub max_y: T,
 
     /// Minimum x-coordinate of the bounding box of the segment
-    pub min_x: f64,
+    pub min_x: T,
 
     /// Minimum y-coordinate of the bounding box of the segment
-    pub min_y: f64,
+    pub min_y: T,
+
+    /// The id of the segment, to trace it through algorithms like bently ottmann
+    ///
+    /// A new segment has the id `0`, use [with_id](LineSegment::with_id) to give it another one.
+    pub id: usize,
 }
 
-/// This trait needs to be implemented to satisfy PartialOrd, it is not yet used.
-impl Eq for LineSegment2D {}
+/// A line segment in a 2D vector space with `f64` coordinates.
+pub type LineSegment2D = LineSegment<f64>;
+
+/// The serialized form of a line segment, the bounding box is rebuilt from the points.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SegmentPoints<T> {
+    p1: Point<T>,
+    p2: Point<T>,
+    id: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> From<SegmentPoints<T>> for LineSegment<T> {
+    fn from(s: SegmentPoints<T>) -> LineSegment<T> {
+        LineSegment::new(s.p1, s.p2).with_id(s.id)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<LineSegment<T>> for SegmentPoints<T> {
+    fn from(s: LineSegment<T>) -> SegmentPoints<T> {
+        SegmentPoints {
+            p1: s.p1,
+            p2: s.p2,
+            id: s.id,
+        }
+    }
+}
+
+/// The result of intersecting two line segments.
+///
+/// Two segments either share no point, exactly one point or, if they are colinear, a whole
+/// overlapping sub-segment.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(
+        serialize = "T: Scalar + serde::Serialize",
+        deserialize = "T: Scalar + serde::Deserialize<'de>"
+    ))
+)]
+pub enum SegmentIntersection<T = f64> {
+    /// The segments share no point.
+    None,
+
+    /// The segments cross or touch in exactly one point.
+    Point(Point<T>),
+
+    /// The segments are colinear and overlap in the contained sub-segment.
+    Overlap(LineSegment<T>),
+}
+
+/// This trait allows intersections to be compared by their kind and geometry.
+impl<T: Scalar> PartialEq for SegmentIntersection<T> {
+    fn eq(&self, other: &SegmentIntersection<T>) -> bool {
+        match (self, other) {
+            (SegmentIntersection::None, SegmentIntersection::None) => true,
+            (SegmentIntersection::Point(p), SegmentIntersection::Point(q)) => p == q,
+            (SegmentIntersection::Overlap(s), SegmentIntersection::Overlap(t)) => s == t,
+            _ => false,
+        }
+    }
+}
+
+/// This trait allows line segments to be compared, two segments are equal iff they are equal in
+/// the [total order](LineSegment::cmp), which means they have the same endpoints. The
+/// [id](LineSegment::id) is not compared, it only labels the segment.
+impl<T: Scalar> PartialEq for LineSegment<T> {
+    fn eq(&self, other: &LineSegment<T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+/// This trait allows line segments to be keys of hashed and sorted collections.
+impl<T: Scalar> Eq for LineSegment<T> {}
+
+/// This trait allows line segments to be hashed consistently with their
+/// [equality](LineSegment::eq), the bounding box is derived from the endpoints.
+impl<T: Scalar> Hash for LineSegment<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.p1.hash(state);
+        self.p2.hash(state);
+    }
+}
 
 /// This trait is implemented to satisfy PartialOrd, and sorts line segments by its starting point.
-impl Ord for LineSegment2D {
-    fn cmp(&self, other: &LineSegment2D) -> Ordering {
-        self.p1.partial_cmp(&other.p1).unwrap()
+///
+/// At tie the end point decides, the [id](LineSegment::id) is ignored. The order is total like
+/// the one of [points](Point::cmp).
+impl<T: Scalar> Ord for LineSegment<T> {
+    fn cmp(&self, other: &LineSegment<T>) -> Ordering {
+        (self.p1, self.p2).cmp(&(other.p1, other.p2))
     }
 }
 
-impl LineSegment2D {
+/// This trait is added to allow line segments to be ordered, see [Ord](LineSegment::cmp).
+impl<T: Scalar> PartialOrd for LineSegment<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Scalar> LineSegment<T> {
     /// Returns a new instance given two points.
     ///
-    /// # Panics
-    /// This function is not allowed to be given two points that are equal.
-    pub fn new(p_a: Point2D, p_b: Point2D) -> LineSegment2D {
-        if p_a == p_b {
-            panic!("A segment with two Points at the same position is not allowed!");
-        }
-
+    /// Two equal points result in a [degenerate](LineSegment::is_degenerate) segment of zero
+    /// length, which still takes part in intersections.
+    pub fn new(p_a: Point<T>, p_b: Point<T>) -> LineSegment<T> {
         let (p1, p2) = if p_a < p_b { (p_a, p_b) } else { (p_b, p_a) };
-        let max_x = p1.x.max(p2.x);
-        let max_y = p1.y.max(p2.y);
-        let min_x = p1.x.min(p2.x);
-        let min_y = p1.y.min(p2.y);
+        let (min_y, max_y) = if p1.y < p2.y {
+            (p1.y, p2.y)
+        } else {
+            (p2.y, p1.y)
+        };
 
-        LineSegment2D {
-            line: Line2D::from_point_and_point(p1, p2),
+        LineSegment {
             p1,
             p2,
-            max_x,
+            max_x: p2.x,
             max_y,
-            min_x,
+            min_x: p1.x,
             min_y,
+            id: 0,
         }
     }
 
-    /// Returns the euclidean distance from start to endpoint.
-    pub fn length_xy(self) -> f64 {
-        self.p1.distance_to(&self.p2)
+    /// Returns a new instance given two points, or an error if a coordinate is NaN or the points
+    /// are equal.
+    ///
+    /// Use [new](LineSegment::new) for [degenerate](LineSegment::is_degenerate) segments of zero
+    /// length.
+    pub fn try_new(p_a: Point<T>, p_b: Point<T>) -> Result<LineSegment<T>, CgError> {
+        if p_a.is_nan() || p_b.is_nan() {
+            return Err(CgError::NanCoordinate);
+        } else if p_a == p_b {
+            return Err(CgError::DegenerateGeometry(format!(
+                "A segment from {p_a} to itself has no length!"
+            )));
+        }
+        Ok(LineSegment::new(p_a, p_b))
+    }
+
+    /// Returns the same segment with the given id.
+    pub fn with_id(mut self, id: usize) -> LineSegment<T> {
+        self.id = id;
+        self
+    }
+
+    /// Returns the line that runs through this segment, directed from `p1` to `p2`.
+    pub fn line(self) -> Line<T> {
+        Line::from_point_and_point(self.p1, self.p2)
+    }
+
+    /// Returns the bounding box of the segment.
+    pub fn bounding_box(self) -> Aabb<T> {
+        Aabb {
+            min: Point {
+                x: self.min_x,
+                y: self.min_y,
+            },
+            max: Point {
+                x: self.max_x,
+                y: self.max_y,
+            },
+        }
+    }
+
+    /// Returns `true` iff both endpoints are equal, so that the segment is a single point.
+    ///
+    /// A degenerate segment counts as [vertical](LineSegment::is_vertical) and its
+    /// [line](LineSegment::line) has only zero coefficients, since it has no direction.
+    pub fn is_degenerate(self) -> bool {
+        self.p1 == self.p2
+    }
+
+    /// Returns `true` iff the segment is parallel to the y-axis.
+    pub fn is_vertical(self) -> bool {
+        self.p1.x == self.p2.x
     }
 
     /// Returns `true` iff the point of the argument is among the segment endpoints.
-    pub fn has_endpoint(self, p: &Point2D) -> bool {
+    pub fn has_endpoint(self, p: &Point<T>) -> bool {
         self.p1 == *p || self.p2 == *p
     }
 
     /// Returns the center point of the line segment.
-    pub fn center(self) -> Point2D {
-        let dx = (self.p1.x - self.p2.x).abs();
-        let dy = (self.p1.y - self.p2.y).abs();
-        Point2D {
-            x: self.p1.x + dx / 2.0,
-            y: self.p1.y + dy / 2.0,
-        }
+    pub fn center(self) -> Point<T> {
+        self.p1 + self.direction() / (T::one() + T::one())
+    }
+
+    /// Returns the vector from the first to the second endpoint.
+    pub fn direction(self) -> Vector<T> {
+        self.p2 - self.p1
     }
 
     /// Returns `true` iff a point is element of the segment.
-    pub fn contains(self, p: &Point2D) -> bool {
+    pub fn contains(self, p: &Point<T>) -> bool {
         self.has_endpoint(p)
-            || (self.line.contains(p)
+            || (T::orientation(&self.p1, &self.p2, p) == T::zero()
                 && (p.x >= self.min_x
                     && p.x <= self.max_x
                     && p.y >= self.min_y
                     && p.y <= self.max_y))
     }
 
-    /// Calculate the intersection point with another line segment.
+    /// Returns `true` iff the segment shares a point with another line segment.
     ///
-    /// This returns an intersection point to another line segment if exists. If not `None` is
-    /// returned. This function uses the counter clock wise ([ccw](crate::tools2d::ccw)) implementation.
-    /// If the lines overlap colinear, also `None` is returned.
-    pub fn intersects(self, other: &LineSegment2D) -> Option<Point2D> {
+    /// This needs only the signs of the [orientations](crate::scalar::Scalar::orientation), so
+    /// unlike [intersects](LineSegment::intersects) it is exact for integers as well.
+    pub fn is_intersecting(self, other: &LineSegment<T>) -> bool {
+        self.classify(other) != Ok(SegmentIntersection::None)
+    }
+
+    /// Returns the intersection with another line segment, or the orientations of the endpoints
+    /// of this segment to the other one if they cross in the interior of both.
+    ///
+    /// The point of such a crossing needs a division, see [intersects](LineSegment::intersects).
+    fn classify(self, other: &LineSegment<T>) -> Result<SegmentIntersection<T>, (T, T)> {
+        // A degenerate segment has no direction, it only intersects where its point lies
+        if self.is_degenerate() || other.is_degenerate() {
+            let (point, segment) = if self.is_degenerate() {
+                (self.p1, *other)
+            } else {
+                (other.p1, self)
+            };
+            if segment.contains(&point) {
+                return Ok(SegmentIntersection::Point(point));
+            }
+            return Ok(SegmentIntersection::None);
+        }
+
         let (p1, p2, q1, q2) = (self.p1, self.p2, other.p1, other.p2);
 
-        if self == *other {
-            return None;
+        let (o1, o2) = (T::orientation(&p1, &p2, &q1), T::orientation(&p1, &p2, &q2));
+        if o1 == T::zero() && o2 == T::zero() {
+            // Both segments are sorted, so the overlap is spanned by the later start point and
+            // the earlier end point
+            let start = p1.max(q1);
+            let end = p2.min(q2);
+            return Ok(match start.cmp(&end) {
+                Ordering::Less => SegmentIntersection::Overlap(LineSegment::new(start, end)),
+                Ordering::Equal => SegmentIntersection::Point(start),
+                Ordering::Greater => SegmentIntersection::None,
+            });
         }
 
         if self.has_endpoint(&q1) {
-            return Some(q1);
+            return Ok(SegmentIntersection::Point(q1));
         }
 
         if self.has_endpoint(&q2) {
-            return Some(q2);
+            return Ok(SegmentIntersection::Point(q2));
         }
 
-        if ccw(&p1, &p2, &q1) * ccw(&p1, &p2, &q2) <= 0.0
-            && ccw(&q1, &q2, &p1) * ccw(&q1, &q2, &p2) <= 0.0
-        {
-            return self.line.intersection(&other.line);
+        // Only the signs of the orientations are compared, they are never multiplied
+        let apart =
+            |a: T, b: T| (a > T::zero() && b > T::zero()) || (a < T::zero() && b < T::zero());
+        let (o3, o4) = (T::orientation(&q1, &q2, &p1), T::orientation(&q1, &q2, &p2));
+        if apart(o1, o2) || apart(o3, o4) {
+            return Ok(SegmentIntersection::None);
         }
-
-        None
+        Err((o3, o4))
     }
 
     /// This prints a geogebra style object that can be copied into the [geogebra calculator](https://www.geogebra.org/calculator).
@@ -152,16 +346,116 @@ impl LineSegment2D {
     }
 }
 
+impl<T: Field> LineSegment<T> {
+    /// Calculate the intersection with another line segment.
+    ///
+    /// This returns the [intersection](SegmentIntersection) with another line segment. This
+    /// function uses the [orientation](crate::scalar::Scalar::orientation) of the scalar, which
+    /// is the exact predicate [orient2d](crate::tools2d::orient2d) for `f64`, so the kind of the
+    /// intersection is always correct, only the point itself is rounded.
+    /// If the lines are colinear, the overlapping sub-segment is returned, or the shared endpoint
+    /// if they only touch. A degenerate segment intersects in its point, if the other segment
+    /// contains it.
+    ///
+    /// The point is only calculated for a [field](crate::scalar::Field), integer segments are
+    /// tested with [is_intersecting](LineSegment::is_intersecting).
+    pub fn intersects(self, other: &LineSegment<T>) -> SegmentIntersection<T> {
+        self.classify(other).unwrap_or_else(|(o3, o4)| {
+            // The point divides the segment in the ratio of the orientations of its endpoints to
+            // the other segment
+            SegmentIntersection::Point(self.p1 + self.direction() * o3 / (o3 - o4))
+        })
+    }
+}
+
+impl<T: Scalar + Float> LineSegment<T> {
+    /// Returns the euclidean distance from start to endpoint.
+    pub fn length_xy(self) -> T {
+        self.p1.distance_to(&self.p2)
+    }
+
+    /// Returns `true` iff a point is element of the segment within the tolerance.
+    ///
+    /// The [distance](crate::distance2d::point_segment) of the point to the segment needs to lie
+    /// within the tolerance.
+    pub fn contains_within(self, p: &Point<T>, tolerance: &Tolerance) -> bool {
+        point_segment(p, &self).distance <= tolerance.bound(p.x.abs().max(p.y.abs()))
+    }
+}
+
+#[cfg(feature = "exact")]
+impl LineSegment2D {
+    /// Calculate the exact intersection with another line segment.
+    ///
+    /// This returns the [exact intersection](crate::exact::ExactIntersection) with rational
+    /// coordinates, or an error if a coordinate is not finite, see
+    /// [intersection](crate::exact::intersection).
+    pub fn intersects_exact(
+        self,
+        other: &LineSegment2D,
+    ) -> Result<crate::exact::ExactIntersection, CgError> {
+        crate::exact::intersection(&self, other)
+    }
+}
+
 /// This trait allows a line segment to be displayed in the form of `p1: (x1,y1), p2: (x2,y2)`.
-impl fmt::Display for LineSegment2D {
+impl<T: Scalar> fmt::Display for LineSegment<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "p1: {}, p2: {}", self.p1, self.p2)
     }
 }
 
+/// This trait allows a line segment to be parsed from its displayed form
+/// `p1: (x1,y1), p2: (x2,y2)` or from the row `x1 y1 x2 y2` of the data files.
+///
+/// The endpoints are sorted like in [new](LineSegment::new) and the id is `0`, since neither form
+/// contains it.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::linesegment2d::LineSegment2D;
+/// let s: LineSegment2D = "36.46 9.133 33.737 13.0919".parse().unwrap();
+/// assert_eq!(s, s.to_string().parse().unwrap());
+/// ```
+impl<T: Scalar + FromStr> FromStr for LineSegment<T> {
+    type Err = CgError;
+
+    fn from_str(s: &str) -> Result<LineSegment<T>, CgError> {
+        if let Some(points) = s.trim().strip_prefix("p1:") {
+            let (p_a, p_b) = points.split_once("p2:").ok_or_else(|| {
+                CgError::Format(format!(
+                    "{s:?} is no segment of the form p1: (x1,y1), p2: (x2,y2)"
+                ))
+            })?;
+            let p_a = p_a.trim_end().strip_suffix(',').unwrap_or(p_a);
+            return Ok(LineSegment::new(p_a.parse()?, p_b.parse()?));
+        }
+
+        let values: Vec<&str> = s.split_whitespace().collect();
+        if values.len() != 4 {
+            return Err(CgError::Format(format!(
+                "expected 4 values, found {}",
+                values.len()
+            )));
+        }
+        Ok(LineSegment::new(
+            Point {
+                x: parse_value(values[0])?,
+                y: parse_value(values[1])?,
+            },
+            Point {
+                x: parse_value(values[2])?,
+                y: parse_value(values[3])?,
+            },
+        ))
+    }
+}
+
 #[cfg(test)]
 mod test_linesegemnt2d {
     use super::*;
+    use crate::point2d::Point2D;
 
     #[test]
     fn test_new() {
@@ -173,18 +467,113 @@ mod test_linesegemnt2d {
         assert_eq!("p1: (0,0), p2: (1,0)", s2.to_string());
         assert_eq!("p1: (0,0), p2: (1,0)", s3.to_string());
         assert_eq!("p1: (0,0), p2: (0,1)", s4.to_string());
+
+        let s5 = LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 1.0, y: 0.0 });
+        assert_eq!(
+            Aabb::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 1.0, y: 2.0 }),
+            s5.bounding_box()
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let s = LineSegment2D::new(
+            Point2D {
+                x: 0.1 + 0.2,
+                y: 9.133,
+            },
+            Point2D { x: -1.0, y: 1e-7 },
+        );
+        assert_eq!(s, s.to_string().parse().unwrap());
+        assert_eq!(
+            s,
+            format!("{} {} {} {}", s.p2.x, s.p2.y, s.p1.x, s.p1.y)
+                .parse()
+                .unwrap()
+        );
+
+        let s: LineSegment<i64> = "p1:(1,2),p2:(0,0)".parse().unwrap();
+        assert_eq!(Point { x: 0, y: 0 }, s.p1);
+
+        assert!("p1: (1,2)".parse::<LineSegment2D>().is_err());
+        assert!("1 2 3".parse::<LineSegment2D>().is_err());
+        assert!("1 2 3 x".parse::<LineSegment2D>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let s =
+            LineSegment2D::new(Point2D { x: 2.0, y: 1.0 }, Point2D { x: 0.0, y: 3.0 }).with_id(4);
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(
+            r#"{"p1":{"x":0.0,"y":3.0},"p2":{"x":2.0,"y":1.0},"id":4}"#,
+            json
+        );
+        assert_eq!(s, serde_json::from_str(&json).unwrap());
+
+        // Unsorted endpoints and a wrong bounding box are rebuilt
+        let json = r#"{"p1":{"x":2.0,"y":1.0},"p2":{"x":0.0,"y":3.0},"id":4,"max_x":9.0}"#;
+        let parsed: LineSegment2D = serde_json::from_str(json).unwrap();
+        assert_eq!(s, parsed);
+        assert_eq!(2.0, parsed.max_x);
+        assert_eq!(1.0, parsed.min_y);
+    }
+
+    #[test]
+    fn test_hash() {
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: -0.0 }, Point2D { x: 1.0, y: 1.0 });
+        let s2 = LineSegment2D::new(Point2D { x: 1.0, y: 1.0 }, Point2D { x: -0.0, y: 0.0 });
+        let s3 = s2.with_id(1);
+        assert_eq!(s1, s2);
+        assert_eq!(s2, s3);
+
+        let set: std::collections::HashSet<LineSegment2D> = [s1, s2, s3].into_iter().collect();
+        assert_eq!(1, set.len());
+    }
+
+    #[test]
+    fn test_try_new() {
+        let p = Point2D { x: 1.0, y: 1.0 };
+        let nan = Point2D {
+            x: f64::NAN,
+            y: 1.0,
+        };
+        assert!(LineSegment2D::try_new(p, Point2D::new()).is_ok());
+        assert!(matches!(
+            LineSegment2D::try_new(p, p),
+            Err(CgError::DegenerateGeometry(_))
+        ));
+        assert!(matches!(
+            LineSegment2D::try_new(p, nan),
+            Err(CgError::NanCoordinate)
+        ));
     }
 
     #[test]
-    #[should_panic]
-    fn test_new_fail() {
-        let _s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 0.0, y: 0.0 });
+    fn test_degenerate() {
+        let p = Point2D { x: 1.0, y: 1.0 };
+        let s1 = LineSegment2D::new(p, p);
+        let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let s3 = LineSegment2D::new(Point2D { x: 2.0, y: 2.0 }, Point2D { x: 3.0, y: 3.0 });
+        assert!(s1.is_degenerate());
+        assert!(s1.is_vertical());
+        assert!(!s2.is_degenerate());
+        assert_eq!(0.0, s1.length_xy());
+        assert!(s1.contains(&p));
+        assert_eq!(SegmentIntersection::Point(p), s1.intersects(&s2));
+        assert_eq!(SegmentIntersection::Point(p), s2.intersects(&s1));
+        assert_eq!(SegmentIntersection::Point(p), s1.intersects(&s1));
+
+        // The point lies on the line of s3, but not on s3 itself
+        assert_eq!(SegmentIntersection::None, s1.intersects(&s3));
+        assert_eq!(SegmentIntersection::None, s3.intersects(&s1));
     }
 
     #[test]
     fn test_line() {
         let s1 = LineSegment2D::new(Point2D { x: 1.0, y: 0.0 }, Point2D { x: 1.0, y: 1.0 });
-        assert_eq!("f(y) -> +1", s1.line.to_string());
+        assert_eq!("-1 * x +0 * y +1 = 0", s1.line().to_string());
     }
 
     #[test]
@@ -200,19 +589,47 @@ mod test_linesegemnt2d {
         assert!(s1 == s2);
     }
 
+    #[test]
+    fn test_order() {
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 1.0, y: 1.0 });
+        let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 1.0, y: 2.0 });
+        let s3 = s2.with_id(1);
+        assert!(s2 > s1);
+        assert_eq!(Ordering::Equal, s3.cmp(&s2));
+        assert_eq!(1, s3.id);
+    }
+
     #[test]
     fn test_center() {
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         assert_eq!(Point2D { x: 1.0, y: 1.0 }, s1.center());
+
+        let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 4.0 }, Point2D { x: 2.0, y: 0.0 });
+        assert_eq!(Point2D { x: 1.0, y: 2.0 }, s2.center());
+        assert_eq!(Vector { x: 2.0, y: -4.0 }, s2.direction());
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_contains() {
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let p1 = Point2D { x: 1.0, y: 1.0 };
         let p2 = Point2D { x: 2.0, y: 1.0 };
         assert_eq!(true, s1.contains(&p1));
         assert_eq!(false, s1.contains(&p2));
+
+        let p3 = Point2D {
+            x: 1.0,
+            y: 1.0 + 1e-10,
+        };
+        let p4 = Point2D {
+            x: 2.0 + 1e-10,
+            y: 2.0 + 1e-10,
+        };
+        assert!(!s1.contains(&p3));
+        assert!(s1.contains_within(&p3, &Tolerance::default()));
+        assert!(s1.contains_within(&p4, &Tolerance::default()));
+        assert!(!s1.contains_within(&p2, &Tolerance::default()));
     }
 
     #[test]
@@ -220,36 +637,111 @@ mod test_linesegemnt2d {
         // s1 and s2 are colinear and they share one endpoint (2,2)
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let s2 = LineSegment2D::new(Point2D { x: 3.0, y: 3.0 }, Point2D { x: 2.0, y: 2.0 });
-        assert_eq!(Some(Point2D { x: 2.0, y: 2.0 }), s1.intersects(&s2));
+        assert_eq!(
+            SegmentIntersection::Point(Point2D { x: 2.0, y: 2.0 }),
+            s1.intersects(&s2)
+        );
 
         // s1 and s2 share one endpoint (0,0)
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: -2.0 });
-        assert_eq!(Some(Point2D { x: 0.0, y: 0.0 }), s1.intersects(&s2));
+        assert_eq!(
+            SegmentIntersection::Point(Point2D { x: 0.0, y: 0.0 }),
+            s1.intersects(&s2)
+        );
 
         // s1 and s2 are colinear and don't overlap
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let s2 = LineSegment2D::new(Point2D { x: 3.0, y: 3.0 }, Point2D { x: 2.5, y: 2.5 });
-        assert_eq!(None, s1.intersects(&s2));
+        assert_eq!(SegmentIntersection::None, s1.intersects(&s2));
 
-        // s1 and s2 are colinear and overlap
+        // s1 and s2 are colinear and overlap from (1.5,1.5) to (2,2)
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let s2 = LineSegment2D::new(Point2D { x: 3.0, y: 3.0 }, Point2D { x: 1.5, y: 1.5 });
-        assert_eq!(None, s1.intersects(&s2));
+        let overlap = LineSegment2D::new(Point2D { x: 1.5, y: 1.5 }, Point2D { x: 2.0, y: 2.0 });
+        assert_eq!(SegmentIntersection::Overlap(overlap), s1.intersects(&s2));
+        assert_eq!(SegmentIntersection::Overlap(overlap), s2.intersects(&s1));
+
+        // s1 and s2 are vertical, colinear and s2 lies within s1
+        let s1 = LineSegment2D::new(Point2D { x: 1.0, y: 0.0 }, Point2D { x: 1.0, y: 4.0 });
+        let s2 = LineSegment2D::new(Point2D { x: 1.0, y: 3.0 }, Point2D { x: 1.0, y: 1.0 });
+        assert_eq!(SegmentIntersection::Overlap(s2), s1.intersects(&s2));
 
         // s1 and s2 share no point
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let s2 = LineSegment2D::new(Point2D { x: -2.0, y: 1.0 }, Point2D { x: 1.0, y: -2.0 });
-        assert_eq!(None, s1.intersects(&s2));
+        assert_eq!(SegmentIntersection::None, s1.intersects(&s2));
 
         // s1 and s2 share one point at (1,1)
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 2.0, y: 0.0 });
-        assert_eq!(Some(Point2D { x: 1.0, y: 1.0 }), s1.intersects(&s2));
+        assert_eq!(
+            SegmentIntersection::Point(Point2D { x: 1.0, y: 1.0 }),
+            s1.intersects(&s2)
+        );
 
         // s1 and s2 are equal
         let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
         let s2 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
-        assert_eq!(None, s1.intersects(&s2));
+        assert_eq!(SegmentIntersection::Overlap(s1), s1.intersects(&s2));
+    }
+
+    #[test]
+    fn test_generic() {
+        // Integer segments are only tested, the crossing of s1 and s3 at (3/2,3/2) is no grid point
+        let s1: LineSegment<i64> = LineSegment::new(Point { x: 0, y: 0 }, Point { x: 4, y: 4 });
+        let s2: LineSegment<i64> = LineSegment::new(Point { x: 0, y: 4 }, Point { x: 4, y: 0 });
+        let s3: LineSegment<i64> = LineSegment::new(Point { x: 0, y: 3 }, Point { x: 3, y: 0 });
+        let s4: LineSegment<i64> = LineSegment::new(Point { x: 5, y: 0 }, Point { x: 5, y: 9 });
+        assert!(s1.is_intersecting(&s2));
+        assert!(s1.is_intersecting(&s3));
+        assert!(!s1.is_intersecting(&s4));
+        assert!(s1.contains(&Point { x: 3, y: 3 }));
+        assert!(!s1.contains(&Point { x: 3, y: 2 }));
+        assert!(s4.is_vertical());
+
+        let s1: LineSegment<f32> =
+            LineSegment::new(Point { x: 0.0, y: 0.0 }, Point { x: 2.0, y: 2.0 });
+        let s2: LineSegment<f32> =
+            LineSegment::new(Point { x: 0.0, y: 2.0 }, Point { x: 2.0, y: 0.0 });
+        assert_eq!(
+            SegmentIntersection::Point(Point { x: 1.0, y: 1.0 }),
+            s1.intersects(&s2)
+        );
+    }
+
+    #[test]
+    fn test_large_integers() {
+        // The orientations of these points overflow i64, but not i128
+        let m = i64::MAX / 4;
+        let s1: LineSegment<i64> = LineSegment::new(Point { x: -m, y: -m }, Point { x: m, y: m });
+        let s2: LineSegment<i64> = LineSegment::new(Point { x: -m, y: m }, Point { x: m, y: -m });
+        let s3: LineSegment<i64> =
+            LineSegment::new(Point { x: -m, y: m }, Point { x: m, y: m - 1 });
+        let s4: LineSegment<i64> =
+            LineSegment::new(Point { x: -m, y: m }, Point { x: m - 1, y: m });
+        assert!(s1.is_intersecting(&s2));
+        assert!(s1.is_intersecting(&s3));
+        assert!(!s1.is_intersecting(&s4));
+        assert!(s1.is_intersecting(&s1.with_id(1)));
+        assert!(s1.contains(&Point { x: m - 1, y: m - 1 }));
+        assert!(!s1.contains(&Point { x: m - 1, y: m }));
+    }
+
+    #[cfg(feature = "exact")]
+    #[test]
+    fn test_rational() {
+        use num_rational::Ratio;
+        let point = |x: i64, y: i64| Point {
+            x: Ratio::from_integer(x),
+            y: Ratio::from_integer(y),
+        };
+        let s1 = LineSegment::new(point(0, 0), point(4, 4));
+        let s2 = LineSegment::new(point(0, 2), point(1, 0));
+        let expected = Point {
+            x: Ratio::new(2, 3),
+            y: Ratio::new(2, 3),
+        };
+        assert_eq!(SegmentIntersection::Point(expected), s1.intersects(&s2));
     }
 }
@@ -2,15 +2,41 @@
 //!
 //! Provides a point struct for the computational geometry library [cg_library](crate).
 
-use crate::tools2d::round_to_decimal_places;
+use crate::error::CgError;
+use crate::scalar::Scalar;
+use crate::tools2d::{parse_value, round_to_decimal_places};
+use crate::vector2d::Vector;
+use num_traits::Float;
 use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::ops::{Add, Sub};
+use std::str::FromStr;
 
 /// A Point in a 2-Dimensional vector space.
 ///
 /// The point is the basis unit of our 2D space and builds the foundament behind various constructs
-/// like [Polygon2D](crate::polygon2d::Polygon2D), [LineSegment2D](crate::linesegment2d::LineSegment2D) and [Line2D](crate::line2d::Line2D).
+/// like [Polygon](crate::polygon2d::Polygon), [LineSegment](crate::linesegment2d::LineSegment) and [Line](crate::line2d::Line).
+/// Its coordinates are of any [scalar](crate::scalar::Scalar) type, [Point2D] is the point with
+/// `f64` coordinates.
+///
+/// # Example
+///
+/// This creates a point on an integer grid:
+/// ```
+/// use cg_library::point2d::Point;
+/// let p: Point<i64> = Point { x: 1, y: 2 };
+/// ```
+#[derive(Default, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point<T> {
+    /// The x-coordinate.
+    pub x: T,
+    /// The y-coordinate.
+    pub y: T,
+}
+
+/// A Point in a 2-Dimensional vector space with `f64` coordinates.
 ///
 /// # Example
 ///
@@ -26,28 +52,49 @@ use std::ops::{Add, Sub};
 /// let p: Point2D = Point2D {x: 1.0, y: 2.0};
 /// ```
 ///
-#[derive(Default, Debug, PartialEq, PartialOrd, Clone, Copy)]
-pub struct Point2D {
-    /// The x-coordinate.
-    pub x: f64,
-    /// The y-coordinate.
-    pub y: f64,
+pub type Point2D = Point<f64>;
+
+/// This trait allows points to be compared, two points are equal iff they are equal in the
+/// [total order](Point::cmp).
+impl<T: Scalar> PartialEq for Point<T> {
+    fn eq(&self, other: &Point<T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
 }
 
-/// This trait needs to be implemented to satisfy PartialOrd, it is not yet used.
-impl Eq for Point2D {}
+/// This trait allows points to be keys of hashed and sorted collections.
+impl<T: Scalar> Eq for Point<T> {}
 
 /// This trait is added to allow points to be ordered.
 ///
-/// Points are ordered after rising x-coordinates at first and at tie after the y-coordinates.
-impl Ord for Point2D {
-    fn cmp(&self, other: &Point2D) -> Ordering {
-        self.partial_cmp(other).unwrap()
+/// Points are ordered after rising x-coordinates at first and at tie after the y-coordinates. The
+/// order is total, since the coordinates are compared [canonically](Scalar::canonical_cmp): `-0.0`
+/// equals `0.0` and NaN is greater than every number.
+impl<T: Scalar> Ord for Point<T> {
+    fn cmp(&self, other: &Point<T>) -> Ordering {
+        self.x
+            .canonical_cmp(&other.x)
+            .then_with(|| self.y.canonical_cmp(&other.y))
+    }
+}
+
+/// This trait is added to allow points to be ordered, see [Ord](Point::cmp).
+impl<T: Scalar> PartialOrd for Point<T> {
+    fn partial_cmp(&self, other: &Point<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// This trait allows points to be hashed consistently with their [equality](Point::eq).
+impl<T: Scalar> Hash for Point<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.canonical_hash(state);
+        self.y.canonical_hash(state);
     }
 }
 
 /// This trait allows implicit addition of one point and another.
-impl Add for Point2D {
+impl<T: Scalar> Add for Point<T> {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
@@ -58,12 +105,13 @@ impl Add for Point2D {
     }
 }
 
-/// This trait allows implicit substraction of one point and another.
-impl Sub for Point2D {
-    type Output = Self;
+/// This trait allows implicit substraction of one point and another, which results in the
+/// [vector](crate::vector2d::Vector) from the other point to this one.
+impl<T: Scalar> Sub for Point<T> {
+    type Output = Vector<T>;
 
-    fn sub(self, other: Self) -> Self {
-        Self {
+    fn sub(self, other: Self) -> Vector<T> {
+        Vector {
             x: self.x - other.x,
             y: self.y - other.y,
         }
@@ -71,47 +119,97 @@ impl Sub for Point2D {
 }
 
 /// This trait allows a point to be displayed in the form of `(x,y)`.
-impl fmt::Display for Point2D {
+impl<T: Scalar> fmt::Display for Point<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({},{})", self.x, self.y)
     }
 }
 
-impl Point2D {
-    /// Returns a Point2D with zeros as default coordinates.
-    pub fn new() -> Point2D {
-        Point2D {
-            ..Default::default()
+/// This trait allows a point to be parsed from its displayed form `(x,y)`.
+///
+/// Whitespace around the coordinates is allowed. Floats are displayed with the shortest digits
+/// that parse to the same value, so points are read back losslessly.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::point2d::Point2D;
+/// let p: Point2D = "(1.5, -2)".parse().unwrap();
+/// assert_eq!(Point2D { x: 1.5, y: -2.0 }, p);
+/// assert_eq!(p, p.to_string().parse().unwrap());
+/// ```
+impl<T: Scalar + FromStr> FromStr for Point<T> {
+    type Err = CgError;
+
+    fn from_str(s: &str) -> Result<Point<T>, CgError> {
+        let (x, y) = s
+            .trim()
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| s.split_once(','))
+            .ok_or_else(|| CgError::Format(format!("{s:?} is no point of the form (x,y)")))?;
+        Ok(Point {
+            x: parse_value(x)?,
+            y: parse_value(y)?,
+        })
+    }
+}
+
+impl<T: Scalar> Point<T> {
+    /// Returns a point with zeros as default coordinates.
+    pub fn new() -> Point<T> {
+        Point {
+            x: T::zero(),
+            y: T::zero(),
         }
     }
 
+    /// Returns a point with the given coordinates.
+    ///
+    /// Points with a NaN coordinate are the result of failed calculations, so they are rejected with
+    /// [NanCoordinate](CgError::NanCoordinate).
+    pub fn try_new(x: T, y: T) -> Result<Point<T>, CgError> {
+        let p = Point { x, y };
+        if p.is_nan() {
+            return Err(CgError::NanCoordinate);
+        }
+        Ok(p)
+    }
+
+    /// Returns `true` iff a coordinate is NaN, which is the only value not comparable to itself.
+    pub fn is_nan(&self) -> bool {
+        self.x.partial_cmp(&self.x).is_none() || self.y.partial_cmp(&self.y).is_none()
+    }
+
     /// Returns `true` iff the points' y-coordinate is greater than the one given in the argument.
-    pub fn is_above_of(&self, other: &Point2D) -> bool {
+    pub fn is_above_of(&self, other: &Point<T>) -> bool {
         self.y > other.y
     }
 
     /// Returns `true` iff the points' y-coordinate is smaller than the one given in the argument.
-    pub fn is_below_of(&self, other: &Point2D) -> bool {
+    pub fn is_below_of(&self, other: &Point<T>) -> bool {
         self.y < other.y
     }
 
     /// Returns `true` iff the points' x-coordinate is smaller than the one given in the argument.
-    pub fn is_left_of(&self, other: &Point2D) -> bool {
+    pub fn is_left_of(&self, other: &Point<T>) -> bool {
         self.x < other.x
     }
 
     /// Returns `true` iff the points' x-coordinate is greater than the one given in the argument.
-    pub fn is_right_of(&self, other: &Point2D) -> bool {
+    pub fn is_right_of(&self, other: &Point<T>) -> bool {
         self.x > other.x
     }
+}
 
+impl<T: Scalar + Float> Point<T> {
     /// Returns the euclidean distance to another point.
-    pub fn distance_to(&self, other: &Point2D) -> f64 {
-        let dx = other.x - self.x;
-        let dy = other.y - self.y;
-        (dx * dx + dy * dy).sqrt()
+    pub fn distance_to(&self, other: &Point<T>) -> T {
+        (*other - *self).norm()
     }
+}
 
+impl Point2D {
     /// Returns an instance of the same point with rounded coordinates.
     pub fn round(&self, decimal_places: u32) -> Point2D {
         let x = round_to_decimal_places(self.x, decimal_places);
@@ -130,6 +228,26 @@ mod test_point2d {
         assert_eq!("(4,8)", p.to_string());
     }
 
+    #[test]
+    fn test_parse() {
+        let p: Point2D = Point2D {
+            x: 0.1 + 0.2,
+            y: -1e-300,
+        };
+        let parsed: Point2D = p.to_string().parse().unwrap();
+        assert_eq!(p.x.to_bits(), parsed.x.to_bits());
+        assert_eq!(p.y.to_bits(), parsed.y.to_bits());
+
+        let p: Point<i64> = " ( 1 ,-2 ) ".parse().unwrap();
+        assert_eq!(Point { x: 1, y: -2 }, p);
+        assert!("(1,2".parse::<Point2D>().is_err());
+        assert!("(1,2,3)".parse::<Point2D>().is_err());
+        assert!(matches!(
+            "(1,x)".parse::<Point2D>(),
+            Err(CgError::Format(_))
+        ));
+    }
+
     #[test]
     fn test_default() {
         let p1: Point2D = Point2D {
@@ -141,6 +259,7 @@ mod test_point2d {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_order() {
         let p0: Point2D = Point2D { x: 0.0, y: 0.0 };
         let p1: Point2D = Point2D { x: 0.0, y: 1.0 };
@@ -154,6 +273,45 @@ mod test_point2d {
     }
 
     #[test]
+    fn test_total_order() {
+        let zero: Point2D = Point2D { x: 0.0, y: 0.0 };
+        let negative_zero: Point2D = Point2D { x: -0.0, y: -0.0 };
+        let nan: Point2D = Point2D {
+            x: f64::NAN,
+            y: 1.0,
+        };
+        assert_eq!(zero, negative_zero);
+        assert_eq!(nan, nan);
+        assert!(
+            nan > Point2D {
+                x: f64::INFINITY,
+                y: 1.0
+            }
+        );
+
+        let mut points = vec![nan, zero, negative_zero, Point2D { x: -1.0, y: 0.0 }];
+        points.sort();
+        points.dedup();
+        assert_eq!(3, points.len());
+        assert_eq!(Point2D { x: -1.0, y: 0.0 }, points[0]);
+        assert!(points[2].is_nan());
+
+        let set: std::collections::HashSet<Point2D> = [
+            zero,
+            negative_zero,
+            nan,
+            Point2D {
+                x: -f64::NAN,
+                y: 1.0,
+            },
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_relation() {
         // Above of and Below of
         let p0: Point2D = Point2D { x: 0.0, y: 0.0 };
@@ -175,4 +333,26 @@ mod test_point2d {
         assert_eq!(false, p1.is_left_of(&p0));
         assert_eq!(false, p0.is_left_of(&p0));
     }
+
+    #[test]
+    fn test_generic() {
+        let p0: Point<i64> = Point { x: 1, y: 2 };
+        let p1: Point<i64> = Point { x: 3, y: -1 };
+        assert_eq!(Point { x: 4, y: 1 }, p0 + p1);
+        assert_eq!(Vector { x: -2, y: 3 }, p0 - p1);
+        assert!(p1 > p0);
+        assert_eq!("(1,2)", p0.to_string());
+
+        assert!(Point::try_new(1, 2).is_ok());
+        assert!(Point::try_new(f64::NAN, 2.0).is_err());
+        assert!(Point {
+            x: 0.0,
+            y: f32::NAN
+        }
+        .is_nan());
+
+        let p0: Point<f32> = Point { x: 0.0, y: 0.0 };
+        let p1: Point<f32> = Point { x: 3.0, y: 4.0 };
+        assert_eq!(5.0, p0.distance_to(&p1));
+    }
 }
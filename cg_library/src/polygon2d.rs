@@ -2,14 +2,26 @@
 //!
 //! Provides a polygon struct for the computational geometry library [cg_library](crate).
 
-use crate::linesegment2d::LineSegment2D;
-use crate::point2d::Point2D;
-use crate::tools2d::ccw;
+use crate::aabb2d::Aabb;
+use crate::error::CgError;
+use crate::linesegment2d::{LineSegment, LineSegment2D, SegmentIntersection};
+use crate::point2d::{Point, Point2D};
+use crate::scalar::{Field, Scalar};
+use crate::tolerance::Tolerance;
+use crate::tools2d::{ccw, orient2d};
+use crate::util::intersection::{Intersection, IntersectionKind};
+use crate::util::sweepline::SweepLine;
+use crate::util::ystructure::YStructure;
+use num_traits::Float;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
 
 /// A polygon in a 2-Dimensional vector space.
 ///
 /// This polygon is the basis unit for regions in a 2D space. It builds the fundament for the usage
-/// of all polygons in a 2D space and can be used for the area of a country.
+/// of all polygons in a 2D space and can be used for the area of a country. Its coordinates are
+/// of any [scalar](crate::scalar::Scalar) type, [Polygon2D] is the polygon with `f64`
+/// coordinates.
 ///
 /// # Example
 ///
@@ -29,27 +41,115 @@ use crate::tools2d::ccw;
 ///
 /// ```
 #[derive(Debug, Clone)]
-pub struct Polygon2D {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "PolygonPoints<T>",
+        into = "PolygonPoints<T>",
+        bound(
+            serialize = "T: Scalar + serde::Serialize",
+            deserialize = "T: Scalar + serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct Polygon<T> {
     /// All points of the polygon
-    pub points: Vec<Point2D>,
+    pub points: Vec<Point<T>>,
 
     /// All segments of the polygon
-    segments: Vec<LineSegment2D>,
+    segments: Vec<LineSegment<T>>,
 
-    /// Maximum x value of the bounding box of the Segment
-    max_x: f64,
+    /// The bounding box of all points
+    bounding_box: Aabb<T>,
+}
 
-    /// Maximum y value of the bounding box of the Segment
-    max_y: f64,
+/// A polygon in a 2-Dimensional vector space with `f64` coordinates.
+pub type Polygon2D = Polygon<f64>;
 
-    /// Minimum x value of the bounding box of the Segment
-    min_x: f64,
+/// The rule that decides which points are inside of a self-intersecting polygon, like the
+/// `fill-rule` of SVG.
+///
+/// Both rules agree on simple polygons. They differ for regions the boundary winds around more
+/// than once, like the center of a pentagram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// Points are inside iff a ray from them crosses the boundary an odd number of times.
+    EvenOdd,
 
-    /// Minimum y value of the bounding box of the Segment
-    min_y: f64,
+    /// Points are inside iff the [winding number](Polygon::winding_number) is not zero, which is
+    /// the default of SVG.
+    #[default]
+    NonZero,
 }
 
-impl Polygon2D {
+impl FillRule {
+    /// Returns `true` iff a point with the winding number is inside under the rule.
+    ///
+    /// The parity of the winding number is the parity of the crossings, so it decides both rules.
+    pub fn is_filled(&self, winding: i32) -> bool {
+        match self {
+            FillRule::EvenOdd => winding % 2 != 0,
+            FillRule::NonZero => winding != 0,
+        }
+    }
+}
+
+/// The location of a point relative to a polygon, see [locate](Polygon::locate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PointLocation {
+    /// The point lies in the interior of the polygon.
+    Inside,
+
+    /// The point lies in the exterior of the polygon.
+    Outside,
+
+    /// The point lies on a vertex or an edge of the polygon.
+    OnBoundary,
+}
+
+/// The direction in which the points of a polygon run around it, see
+/// [orientation](Polygon::orientation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Orientation {
+    /// The points run counter clock wise, the signed area is positive.
+    CounterClockwise,
+
+    /// The points run clock wise, the signed area is negative.
+    Clockwise,
+
+    /// The signed area is zero, like for points on a line or a bow tie with equal halves.
+    Degenerate,
+}
+
+/// The serialized form of a polygon, the segments and the bounding box are rebuilt from the
+/// points, which are validated like in [try_new](Polygon::try_new).
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PolygonPoints<T> {
+    points: Vec<Point<T>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Scalar> TryFrom<PolygonPoints<T>> for Polygon<T> {
+    type Error = CgError;
+
+    fn try_from(p: PolygonPoints<T>) -> Result<Polygon<T>, CgError> {
+        Polygon::try_new(p.points)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<Polygon<T>> for PolygonPoints<T> {
+    fn from(p: Polygon<T>) -> PolygonPoints<T> {
+        PolygonPoints { points: p.points }
+    }
+}
+
+impl<T: Scalar> Polygon<T> {
     /// Returns an instance of a polygon initialized with a vector of points.
     ///
     /// If the first point of the vector does not fit the last, the first is appended to make a
@@ -57,40 +157,47 @@ impl Polygon2D {
     ///
     /// # Panics
     ///
-    /// This function will panic if there are not at least $2$ points.
-    pub fn new(mut points: Vec<Point2D>) -> Polygon2D {
+    /// This function will panic if there are not at least $3$ points or a coordinate is NaN, see
+    /// [try_new](Polygon::try_new).
+    pub fn new(points: Vec<Point<T>>) -> Polygon<T> {
+        Polygon::try_new(points).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Returns an instance of a polygon initialized with a vector of points, or an error if there
+    /// are not at least $3$ points or a coordinate is NaN.
+    ///
+    /// If the first point of the vector does not fit the last, the first is appended to make a
+    /// closed polygon.
+    pub fn try_new(mut points: Vec<Point<T>>) -> Result<Polygon<T>, CgError> {
         if points.len() <= 2 {
-            panic!("A polygon consisting of two points is no polygon!");
+            return Err(CgError::DegenerateGeometry(String::from(
+                "A polygon consisting of two points is no polygon!",
+            )));
+        } else if points.iter().any(Point::is_nan) {
+            return Err(CgError::NanCoordinate);
         } else if points.first() != points.last() {
-            println!("The first point does not match the last point!\nIt gets appended now!");
             points.push(*points.first().unwrap());
         }
 
-        let mut segments: Vec<LineSegment2D> = Vec::new();
+        let mut segments: Vec<LineSegment<T>> = Vec::new();
 
         let mut old_point = points.first().unwrap();
-
-        let mut min_x = std::f64::MAX.min(old_point.x);
-        let mut max_x = std::f64::MIN.max(old_point.x);
-        let mut min_y = std::f64::MAX.min(old_point.y);
-        let mut max_y = std::f64::MIN.max(old_point.y);
         for point in points.iter().skip(1) {
-            segments.push(LineSegment2D::new(*old_point, *point));
+            segments.push(LineSegment::new(*old_point, *point));
             old_point = point;
-            min_x = min_x.min(point.x);
-            max_x = max_x.max(point.x);
-            min_y = min_y.min(point.y);
-            max_y = max_y.max(point.y);
         }
+        let bounding_box = Aabb::from_points(&points).unwrap();
 
-        return Polygon2D {
+        Ok(Polygon {
             points,
             segments,
-            max_x,
-            max_y,
-            min_x,
-            min_y,
-        };
+            bounding_box,
+        })
+    }
+
+    /// Returns the bounding box of the polygon.
+    pub fn bounding_box(&self) -> Aabb<T> {
+        self.bounding_box
     }
 
     /// Prints out all the points and segments of the polygon.
@@ -105,88 +212,676 @@ impl Polygon2D {
         }
     }
 
-    /// Returns `true` iff a point `p` is inside a polygon.
+    /// Returns the winding number of the polygon around a point.
     ///
-    /// This does not work for all points ontop of the polygon.
-    pub fn contains(&self, q: &Point2D) -> bool {
-        // Get a point outside of the polygon
-        let p_outside: Point2D = Point2D {
-            x: self.max_x + 1.0,
-            y: self.max_y + 1.0,
-        };
+    /// It counts how often the boundary winds counter clock wise around the point, clock wise
+    /// turns count negative. The number is only meaningful for points that are not on the
+    /// boundary.
+    pub fn winding_number(&self, p: &Point<T>) -> i32 {
+        let mut winding = 0;
+        for edge in self.points.windows(2) {
+            let (a, b) = (&edge[0], &edge[1]);
+            if a.y <= p.y {
+                // An upward edge that has the point on its left side
+                if b.y > p.y && T::orientation(a, b, p) > T::zero() {
+                    winding += 1;
+                }
+            } else if b.y <= p.y && T::orientation(a, b, p) < T::zero() {
+                // A downward edge that has the point on its right side
+                winding -= 1;
+            }
+        }
+        winding
+    }
+
+    /// Returns the location of a point relative to the polygon.
+    ///
+    /// Points on a vertex or an edge are [on the boundary](PointLocation::OnBoundary), all other
+    /// points are inside iff the [winding number](Polygon::winding_number) is not zero, see
+    /// [locate_with](Polygon::locate_with) for other fill rules. The
+    /// orientations are evaluated with the [orientation](crate::scalar::Scalar::orientation) of
+    /// the scalar, which is the exact predicate [orient2d](crate::tools2d::orient2d) for `f64`, so
+    /// the boundary is detected exactly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cg_library::point2d::Point;
+    /// use cg_library::polygon2d::{Polygon, PointLocation};
+    ///
+    /// let square: Polygon<i64> = Polygon::new(vec![
+    ///     Point { x: 0, y: 0 },
+    ///     Point { x: 2, y: 0 },
+    ///     Point { x: 2, y: 2 },
+    ///     Point { x: 0, y: 2 },
+    /// ]);
+    /// assert_eq!(PointLocation::Inside, square.locate(&Point { x: 1, y: 1 }));
+    /// assert_eq!(PointLocation::OnBoundary, square.locate(&Point { x: 2, y: 1 }));
+    /// assert_eq!(PointLocation::Outside, square.locate(&Point { x: 3, y: 1 }));
+    /// ```
+    pub fn locate(&self, p: &Point<T>) -> PointLocation {
+        self.locate_with(p, FillRule::NonZero)
+    }
 
-        // Retrieve polygon point that is not part of the segment p_outside q
-        let mut i = 0;
-        while 0.0 == ccw(&p_outside, &q, &self.points[i]) {
-            i += 1;
+    /// Returns the location of a point relative to the polygon, where the points that are not on
+    /// the boundary are inside iff they are [filled](FillRule::is_filled) under the rule.
+    pub fn locate_with(&self, p: &Point<T>, rule: FillRule) -> PointLocation {
+        if !self.bounding_box.contains_point(p) {
+            return PointLocation::Outside;
+        }
+        let on_boundary = self.segments.iter().any(|s| {
+            s.bounding_box().contains_point(p) && T::orientation(&s.p1, &s.p2, p) == T::zero()
+        });
+        if on_boundary {
+            PointLocation::OnBoundary
+        } else if rule.is_filled(self.winding_number(p)) {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
         }
+    }
 
-        let mut s = 0;
-        let mut lr = ccw(&p_outside, q, &self.points[i]).signum();
-        for j in (i + 1)..self.points.len() {
-            // let g = if j == self.points.len() {0} else {j};
-            let lrnew = ccw(&p_outside, q, &self.points[j]).signum();
-            // println!("\nlr: {}, lrnew: {}, j: {}",&lr, &lrnew, &j);
-            if (lrnew - lr).abs() == 2.0 {
-                lr = lrnew;
-                if ccw(&self.points[j - 1], &self.points[j], &p_outside)
-                    * ccw(&self.points[j - 1], &self.points[j], &q)
-                    <= 0.0
-                {
-                    s += 1;
+    /// Returns `true` iff a point `p` is inside or ontop of the polygon, see
+    /// [locate](Polygon::locate).
+    pub fn contains(&self, q: &Point<T>) -> bool {
+        self.locate(q) != PointLocation::Outside
+    }
+
+    /// Returns `true` iff a point `p` is inside or ontop of the polygon under the
+    /// [even-odd](FillRule::EvenOdd) rule, see [contains_point_with](Polygon::contains_point_with).
+    pub fn contains_point(&self, p: &Point<T>) -> bool {
+        self.contains_point_with(p, FillRule::EvenOdd)
+    }
+
+    /// Returns `true` iff a point `p` is inside or ontop of the polygon under a fill rule.
+    pub fn contains_point_with(&self, p: &Point<T>, rule: FillRule) -> bool {
+        self.locate_with(p, rule) != PointLocation::Outside
+    }
+
+    /// Returns the [signed area](Polygon::signed_area) of the polygon, which is positive iff the
+    /// polygon is counter clockwise.
+    pub fn calculate_area(&self) -> T {
+        self.signed_area()
+    }
+
+    /// Returns the signed area of the polygon by the shoelace formula.
+    ///
+    /// The area is positive if the polygon is counter clockwise and negative if it is clockwise.
+    /// The area of integer polygons is truncated, since it is half of an integer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cg_library::point2d::Point;
+    /// use cg_library::polygon2d::Polygon;
+    ///
+    /// let ccw = Polygon::new(vec![
+    ///     Point { x: 0, y: 0 },
+    ///     Point { x: 2, y: 0 },
+    ///     Point { x: 2, y: 2 },
+    ///     Point { x: 0, y: 2 },
+    /// ]);
+    /// assert_eq!(4, ccw.signed_area());
+    /// assert_eq!(-4, ccw.reverse().signed_area());
+    /// assert_eq!(4, ccw.reverse().area());
+    /// ```
+    pub fn signed_area(&self) -> T {
+        self.doubled_area() / (T::one() + T::one())
+    }
+
+    /// Returns twice the signed area of the polygon, the sum of the shoelace formula.
+    ///
+    /// Unlike the [signed area](Polygon::signed_area) it is exact for integer polygons.
+    fn doubled_area(&self) -> T {
+        let mut area = T::zero();
+        for i in 0..(self.points.len() - 1) {
+            area = area + ccw(&Point::new(), &self.points[i], &self.points[i + 1]);
+        }
+        area
+    }
+
+    /// Returns the area of the polygon, which is the absolute value of the
+    /// [signed area](Polygon::signed_area).
+    ///
+    /// Regions of self-intersecting polygons can cancel each other out, see
+    /// [area_with](Polygon::area_with) for the area of those.
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+
+    /// Returns the direction in which the points run around the polygon, which is the sign of the
+    /// [signed area](Polygon::signed_area).
+    ///
+    /// The sign is taken before the area is halved, so that integer polygons of area `1/2` keep
+    /// their orientation.
+    pub fn orientation(&self) -> Orientation {
+        let area = self.doubled_area();
+        if area > T::zero() {
+            Orientation::CounterClockwise
+        } else if area < T::zero() {
+            Orientation::Clockwise
+        } else {
+            Orientation::Degenerate
+        }
+    }
+
+    /// Returns `true` iff the polygon is counter clockwise.
+    pub fn is_ccw(&self) -> bool {
+        self.orientation() == Orientation::CounterClockwise
+    }
+
+    /// Returns the polygon with the points in reversed order, which flips its orientation.
+    pub fn reverse(&self) -> Polygon<T> {
+        let mut points = self.points.clone();
+        points.reverse();
+        Polygon::new(points)
+    }
+
+    /// Returns the polygon counter clockwise, like the shells of GeoJSON.
+    ///
+    /// Only clockwise polygons are reversed, [degenerate](Orientation::Degenerate) ones are kept.
+    pub fn to_ccw(&self) -> Polygon<T> {
+        if self.orientation() == Orientation::Clockwise {
+            self.reverse()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns the polygon clockwise, like the holes of GeoJSON.
+    ///
+    /// Only counter clockwise polygons are reversed, [degenerate](Orientation::Degenerate) ones
+    /// are kept.
+    pub fn to_cw(&self) -> Polygon<T> {
+        if self.orientation() == Orientation::CounterClockwise {
+            self.reverse()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Returns `true` iff all points of another polygon is inside the polygon.
+    #[allow(clippy::needless_return)]
+    pub fn contains_polygon(&self, poly: &Polygon<T>) -> bool {
+        if !self.bounding_box.contains_box(&poly.bounding_box) {
+            return false;
+        }
+        for point in &poly.points {
+            if !self.contains_point(point) {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+impl<T: Field + Float> Polygon<T> {
+    /// Returns the location of a point relative to the polygon like [locate](Polygon::locate),
+    /// but points within the tolerance of an edge are [on the boundary](PointLocation::OnBoundary).
+    ///
+    /// This accepts points that miss the boundary only because of rounded coordinates, like the
+    /// intersection points of [bently_ottmann](crate::tools2d::bently_ottmann).
+    pub fn locate_within(&self, p: &Point<T>, tolerance: &Tolerance) -> PointLocation {
+        if self.is_on_boundary_within(p, tolerance) {
+            PointLocation::OnBoundary
+        } else {
+            self.locate(p)
+        }
+    }
+
+    /// Returns `true` iff a point `p` is inside or ontop of the polygon within the tolerance, see
+    /// [locate_within](Polygon::locate_within).
+    pub fn contains_within(&self, q: &Point<T>, tolerance: &Tolerance) -> bool {
+        self.locate_within(q, tolerance) != PointLocation::Outside
+    }
+
+    /// Returns `true` iff a point `p` is inside or ontop of the polygon within the tolerance, like
+    /// [contains_point](Polygon::contains_point).
+    pub fn contains_point_within(&self, p: &Point<T>, tolerance: &Tolerance) -> bool {
+        self.is_on_boundary_within(p, tolerance) || self.contains_point(p)
+    }
+
+    /// Returns `true` iff a point lies on an edge within the tolerance.
+    fn is_on_boundary_within(&self, p: &Point<T>, tolerance: &Tolerance) -> bool {
+        self.segments
+            .iter()
+            .any(|s| s.contains_within(p, tolerance))
+    }
+
+    /// Returns the area of all regions that are filled under a rule.
+    ///
+    /// Unlike the [signed area](Polygon::signed_area) the area is always positive and
+    /// regions of self-intersecting polygons count once, no matter how often the boundary winds
+    /// around them. The edges are cut at every self-intersection into pieces that do not cross,
+    /// which are swept from left to right in an ordered set. The winding number below a piece is
+    /// the one above its lower neighbor, so every piece that bounds a filled region from above
+    /// adds the area below it and every piece that bounds one from below subtracts it. This runs in
+    /// $O((n+k) \log n)$ for $n$ edges and $k$ self-intersections.
+    ///
+    /// The crossing edges are found with [self_intersections](Polygon2D::self_intersections), but
+    /// their points are recalculated with the coordinates of the polygon, since the sweep line
+    /// merges points that are equal within its tolerance. A nearly colinear contact, that the
+    /// sweep line misses, is not cut, so the area is only as reliable as the self-intersections.
+    ///
+    /// # Example
+    ///
+    /// The bow tie consists of two triangles, whose signed areas cancel each other out:
+    /// ```
+    /// use cg_library::point2d::Point2D;
+    /// use cg_library::polygon2d::{FillRule, Polygon2D};
+    ///
+    /// let bow_tie = Polygon2D::new(vec![
+    ///     Point2D { x: 0.0, y: 0.0 },
+    ///     Point2D { x: 2.0, y: 2.0 },
+    ///     Point2D { x: 2.0, y: 0.0 },
+    ///     Point2D { x: 0.0, y: 2.0 },
+    /// ]);
+    /// assert_eq!(0.0, bow_tie.signed_area());
+    /// assert_eq!(2.0, bow_tie.area_with(FillRule::NonZero));
+    /// ```
+    pub fn area_with(&self, rule: FillRule) -> T {
+        // The sweep line works on `f64`, which holds the coordinates of both float types exactly
+        let ring = Polygon2D::new(
+            self.points
+                .iter()
+                .map(|p| Point2D {
+                    x: p.x.to_f64().unwrap(),
+                    y: p.y.to_f64().unwrap(),
+                })
+                .collect(),
+        );
+        let mut cuts: Vec<Vec<T>> = vec![Vec::new(); self.segments.len()];
+        for intersection in ring.self_intersections() {
+            let edges = &intersection.segments;
+            for (k, &i) in edges.iter().enumerate() {
+                for &j in &edges[k + 1..] {
+                    let xs = match self.segments[i].intersects(&self.segments[j]) {
+                        SegmentIntersection::Point(p) => vec![p.x],
+                        SegmentIntersection::Overlap(o) => vec![o.p1.x, o.p2.x],
+                        SegmentIntersection::None => Vec::new(),
+                    };
+                    cuts[i].extend(xs.iter().copied());
+                    cuts[j].extend(xs);
                 }
             }
         }
-        return s % 2 == 1;
+
+        // The crossings of different pairs of edges through the same point differ in the last
+        // bits, so their pieces would cross
+        let scale = self
+            .points
+            .iter()
+            .fold(T::zero(), |scale, p| scale.max(p.x.abs()));
+        let gap = T::epsilon() * T::from(64).unwrap() * scale;
+        snap(&mut cuts, self.points.iter().map(|p| p.x).collect(), gap);
+
+        // Vertical edges have no width, the others wind positive from left to right
+        let mut pieces: Vec<Piece<T>> = Vec::new();
+        for (edge, xs) in self.points.windows(2).zip(cuts.iter_mut()) {
+            let (a, b, winding) = match edge[0].x.canonical_cmp(&edge[1].x) {
+                Ordering::Less => (edge[0], edge[1], 1),
+                Ordering::Greater => (edge[1], edge[0], -1),
+                Ordering::Equal => continue,
+            };
+            xs.retain(|x| a.x < *x && *x < b.x);
+            xs.extend([a.x, b.x]);
+            xs.sort_by(T::canonical_cmp);
+            xs.dedup();
+            for slab in xs.windows(2) {
+                pieces.push(Piece {
+                    a,
+                    b,
+                    left: slab[0],
+                    right: slab[1],
+                    winding,
+                    index: pieces.len(),
+                });
+            }
+        }
+
+        // Pieces that end at an x-coordinate leave the set before the ones that start there are
+        // inserted from bottom to top
+        let mut starts: Vec<&Piece<T>> = pieces.iter().collect();
+        starts.sort_by(|p, q| p.left.canonical_cmp(&q.left).then_with(|| p.cmp(q)));
+        let mut ends: Vec<&Piece<T>> = pieces.iter().collect();
+        ends.sort_by(|p, q| p.right.canonical_cmp(&q.right));
+
+        let two = T::one() + T::one();
+        let mut above: Vec<i32> = vec![0; pieces.len()];
+        let mut active: BTreeSet<&Piece<T>> = BTreeSet::new();
+        let mut area = T::zero();
+        let mut ends = ends.into_iter().peekable();
+        for piece in starts {
+            while let Some(end) = ends.next_if(|end| end.right <= piece.left) {
+                active.remove(end);
+            }
+            let below = active
+                .range::<&Piece<T>, _>(..piece)
+                .next_back()
+                .map_or(0, |lower| above[lower.index]);
+            above[piece.index] = below + piece.winding;
+            let (filled_below, filled_above) =
+                (rule.is_filled(below), rule.is_filled(below + piece.winding));
+            if filled_below != filled_above {
+                let (left, right) = (piece.left, piece.right);
+                let under = (piece.y_at(left) + piece.y_at(right)) / two * (right - left);
+                area = if filled_below {
+                    area + under
+                } else {
+                    area - under
+                };
+            }
+            active.insert(piece);
+        }
+        area
     }
+}
 
-    /// Returns `true` iff a point `p` is inside or ontop of the polygon.
-    pub fn contains_point(&self, p: &Point2D) -> bool {
-        let mut crossings = 0;
-        let n = self.points.len();
+/// Snaps the x-coordinates of the cuts, that lie within a gap of each other, to one of them, see
+/// [area_with](Polygon::area_with).
+///
+/// The x-coordinates of vertices are kept, the cuts next to them are snapped to them. The other
+/// cuts are snapped to the first one of their cluster.
+fn snap<T: Field + Float>(cuts: &mut [Vec<T>], mut vertices: Vec<T>, gap: T) {
+    vertices.sort_by(T::canonical_cmp);
+    let mut xs: Vec<T> = cuts.iter().flatten().copied().collect();
+    xs.sort_by(T::canonical_cmp);
 
-        for i in 0..n {
-            let j = (i + 1) % n;
+    let nearest_vertex = |x: T| {
+        let i = vertices.partition_point(|v| *v < x);
+        [i.checked_sub(1), Some(i)]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| vertices.get(i).copied())
+            .find(|v| (*v - x).abs() <= gap)
+    };
+    let mut snapped: Vec<(T, T)> = Vec::with_capacity(xs.len());
+    let mut first: Option<T> = None;
+    for x in xs {
+        let target = match nearest_vertex(x) {
+            Some(v) => v,
+            None => match first {
+                Some(f) if x - f <= gap => f,
+                _ => {
+                    first = Some(x);
+                    x
+                }
+            },
+        };
+        snapped.push((x, target));
+    }
+    for x in cuts.iter_mut().flatten() {
+        let i = snapped.partition_point(|(y, _)| *y < *x);
+        *x = snapped[i].1;
+    }
+}
+
+/// The part of an edge between two self-intersections, see [area_with](Polygon::area_with).
+///
+/// Pieces are ordered from bottom to top, which is consistent for all pieces that are swept at
+/// the same time, since they do not cross. If one edge lies on one side of the line through the
+/// other, the side is decided exactly with the [orientation](Scalar::orientation). Only crossing
+/// edges are compared in the middle of the x-range their pieces share. At tie the index decides.
+#[derive(Debug)]
+struct Piece<T> {
+    /// The left endpoint of the edge.
+    a: Point<T>,
+
+    /// The right endpoint of the edge.
+    b: Point<T>,
+
+    /// The x-coordinate where the piece starts.
+    left: T,
+
+    /// The x-coordinate where the piece ends.
+    right: T,
+
+    /// The winding of the edge, `1` if it runs from left to right and `-1` otherwise.
+    winding: i32,
+
+    /// The index of the piece.
+    index: usize,
+}
+
+impl<T: Field + Float> Piece<T> {
+    /// Returns the y-coordinate of the edge at an x-coordinate.
+    fn y_at(&self, x: T) -> T {
+        self.a.y + (self.b.y - self.a.y) * (x - self.a.x) / (self.b.x - self.a.x)
+    }
+
+    /// Returns whether the edge of another piece lies above or below the line through this
+    /// edge, `Equal` if it lies on the line and `None` if it crosses the line.
+    fn side_of(&self, other: &Piece<T>) -> Option<Ordering> {
+        let side = |p: &Point<T>| T::orientation(&self.a, &self.b, p).canonical_cmp(&T::zero());
+        match (side(&other.a), side(&other.b)) {
+            (Ordering::Less, Ordering::Greater) | (Ordering::Greater, Ordering::Less) => None,
+            (a, b) => Some(a.then(b)),
+        }
+    }
+}
+
+impl<T: Field + Float> PartialEq for Piece<T> {
+    fn eq(&self, other: &Piece<T>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: Field + Float> Eq for Piece<T> {}
+
+impl<T: Field + Float> Ord for Piece<T> {
+    fn cmp(&self, other: &Piece<T>) -> Ordering {
+        match (self.side_of(other), other.side_of(self)) {
+            (Some(Ordering::Equal), _) => self.index.cmp(&other.index),
+            (Some(side), _) => side.reverse(),
+            (_, Some(side)) => side,
+            (None, None) => {
+                let two = T::one() + T::one();
+                let x = (self.left.max(other.left) + self.right.min(other.right)) / two;
+                self.y_at(x)
+                    .canonical_cmp(&other.y_at(x))
+                    .then_with(|| self.index.cmp(&other.index))
+            }
+        }
+    }
+}
+
+impl<T: Field + Float> PartialOrd for Piece<T> {
+    fn partial_cmp(&self, other: &Piece<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Polygon2D {
+    /// Returns a sweep line with the edges of the polygon, the id of every edge is its index.
+    fn edge_sweep(&self) -> SweepLine {
+        let mut sl = SweepLine::new();
+        for (id, edge) in self.segments.iter().enumerate() {
+            sl.insert_segment(edge.with_id(id));
+        }
+        sl
+    }
+
+    /// Returns `true` iff an intersection is only the shared vertex of two adjacent edges.
+    ///
+    /// An edge of zero length has no vertex of its own, so it shares none.
+    fn is_shared_vertex(&self, intersection: &Intersection) -> bool {
+        let n = self.segments.len();
+        match intersection.segments[..] {
+            [i, j] => {
+                intersection.kind == IntersectionKind::Point
+                    && (j == i + 1 || j - i == n - 1)
+                    && !self.segments[i].is_degenerate()
+                    && !self.segments[j].is_degenerate()
+            }
+            _ => false,
+        }
+    }
 
-            if (self.points[i].y <= p.y && p.y < self.points[j].y)
-                || (self.points[j].y <= p.y && p.y < self.points[i].y)
-            {
-                if p.x
-                    < (self.points[j].x - self.points[i].x) * (p.y - self.points[i].y)
-                        / (self.points[j].y - self.points[i].y)
-                        + self.points[i].x
+    /// Returns all points where the boundary of the polygon touches or crosses itself.
+    ///
+    /// The intersections are found with the [sweep line](SweepLine), the segments of every
+    /// intersection are the indices of the edges, where edge `i` runs from point `i` to point
+    /// `i + 1`. The vertex that two adjacent edges share is no self-intersection, but a vertex
+    /// that is visited twice is, as well as a duplicate point, which is an edge of zero length.
+    ///
+    /// The sweep line snaps intersection points to the decimal places of its
+    /// [tolerance](crate::tolerance::Tolerance), which moves them off the edges. Therefore every
+    /// point is recalculated from the first two edges of its intersection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cg_library::point2d::Point2D;
+    /// use cg_library::polygon2d::Polygon2D;
+    ///
+    /// let bow_tie = Polygon2D::new(vec![
+    ///     Point2D { x: 0.0, y: 0.0 },
+    ///     Point2D { x: 2.0, y: 2.0 },
+    ///     Point2D { x: 2.0, y: 0.0 },
+    ///     Point2D { x: 0.0, y: 2.0 },
+    /// ]);
+    /// let intersections = bow_tie.self_intersections();
+    /// assert_eq!(Point2D { x: 1.0, y: 1.0 }, intersections[0].point);
+    /// assert_eq!(vec![0, 2], intersections[0].segments);
+    /// ```
+    pub fn self_intersections(&self) -> Vec<Intersection> {
+        let mut sl = self.edge_sweep();
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+        let mut intersections: Vec<Intersection> = sl
+            .intersections
+            .into_iter()
+            .filter(|i| !self.is_shared_vertex(i))
+            .collect();
+        for intersection in intersections.iter_mut() {
+            if intersection.kind != IntersectionKind::Point {
+                continue;
+            }
+            if let [i, j, ..] = intersection.segments[..] {
+                if let SegmentIntersection::Point(p) =
+                    self.segments[i].intersects(&self.segments[j])
                 {
-                    crossings += 1;
+                    intersection.point = p;
                 }
             }
         }
-
-        crossings % 2 == 1
+        intersections.sort();
+        intersections
     }
 
-    /// Returns the area of the polygon, if the polygon is counter clockwise the area is negative.
-    pub fn calculate_area(&self) -> f64 {
-        let mut area = 0.0;
-        for i in 0..(self.points.len() - 1) {
-            area += ccw(&&Point2D::new(), &self.points[i], &self.points[i + 1]);
+    /// Returns `true` iff two edges meet in more than the shared vertex of adjacent edges.
+    fn is_touching(&self, a: &LineSegment2D, b: &LineSegment2D) -> bool {
+        let n = self.segments.len();
+        let (i, j) = (a.id.min(b.id), a.id.max(b.id));
+        match a.intersects(b) {
+            SegmentIntersection::None => false,
+            SegmentIntersection::Point(_) => j != i + 1 && j - i != n - 1,
+            SegmentIntersection::Overlap(_) => true,
         }
-        return area * 0.5;
     }
 
-    /// Returns `true` iff all points of another polygon is inside the polygon.
-    pub fn contains_polygon(&self, poly: &Polygon2D) -> bool {
-        for point in &poly.points {
-            if !self.contains_point(point) {
+    /// Returns `true` iff the boundary of the polygon does not touch or cross itself, see
+    /// [self_intersections](Polygon2D::self_intersections).
+    ///
+    /// This is the algorithm of Shamos and Hoey, it stops at the first self-intersection. Unlike
+    /// the [sweep line](SweepLine) it has no intersection events, the edges are only tested when
+    /// they become neighbors in the [y-structure](YStructure). Until the first self-intersection
+    /// no two edges swap, so a polygon with $n$ points is checked in $O(n \log n)$.
+    ///
+    /// At every point the ending edges are removed before the starting edges are inserted, which
+    /// are tested against the ended ones. Vertical edges are not part of the y-structure, they
+    /// are tested against the edges that cross their x-coordinate within their range.
+    pub fn is_simple(&self) -> bool {
+        let edges: Vec<LineSegment2D> = self
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(id, edge)| edge.with_id(id))
+            .collect();
+
+        // A duplicate point is an edge of zero length, in a triangle its neighbors are adjacent
+        if edges.iter().any(|edge| edge.is_degenerate()) {
+            return false;
+        }
+
+        // The events are ordered after their point, removals before insertions
+        let mut events: Vec<(Point2D, bool, LineSegment2D)> = Vec::new();
+        for edge in &edges {
+            events.push((edge.p1, true, *edge));
+            if edge.p1.x != edge.p2.x {
+                events.push((edge.p2, false, *edge));
+            }
+        }
+        events.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+        let mut ys: YStructure = YStructure::new();
+        let mut verticals: Vec<LineSegment2D> = Vec::new();
+        let mut ended: Vec<LineSegment2D> = Vec::new();
+        let mut current = events.first().map(|e| e.0);
+        for (p, insert, edge) in events {
+            if Some(p) != current {
+                if current.map(|c| c.x) != Some(p.x) {
+                    verticals.clear();
+                }
+                ended.clear();
+                current = Some(p);
+            }
+            ys.set_position(p);
+            verticals.retain(|v| v.max_y >= p.y);
+
+            if !insert {
+                let (prev, next) = (ys.prev(&edge), ys.next(&edge));
+                ys.remove(&edge);
+                ended.push(edge);
+                if let (Some(prev), Some(next)) = (prev, next) {
+                    if self.is_touching(&prev, &next) {
+                        return false;
+                    }
+                }
+                continue;
+            }
+
+            let mut others: Vec<LineSegment2D> = verticals.clone();
+            others.extend(ended.iter().copied());
+            if edge.p1.x == edge.p2.x {
+                // All edges of the y-structure between the endpoints of the vertical edge
+                let mut above = ys.first_from(edge.min_y);
+                while let Some(other) = above {
+                    if orient2d(&other.p1, &other.p2, &edge.p2) < 0.0 {
+                        break;
+                    }
+                    others.push(other);
+                    above = ys.next(&other);
+                }
+                verticals.push(edge);
+            } else {
+                ys.insert(edge);
+                others.extend(ys.prev(&edge));
+                others.extend(ys.next(&edge));
+            }
+            if others.iter().any(|other| self.is_touching(&edge, other)) {
                 return false;
             }
         }
-        return true;
+        true
+    }
+}
+
+#[cfg(feature = "exact")]
+impl Polygon2D {
+    /// Returns the exact area of the polygon as a rational number, or an error if a coordinate is
+    /// infinite, see [signed_area](Polygon::signed_area).
+    pub fn calculate_area_exact(&self) -> Result<num_rational::BigRational, CgError> {
+        crate::exact::area(&self.points)
     }
 }
 
 #[cfg(test)]
 mod test_polygon {
     use super::*;
+    use crate::point2d::Point2D;
+    use crate::tools2d::splitmix64;
 
     #[test]
     fn test_new() {
@@ -198,7 +893,11 @@ mod test_polygon {
             Point2D { x: 3.0, y: 3.0 },
         ];
 
-        let _ = Polygon2D::new(points);
+        let poly = Polygon2D::new(points);
+        assert_eq!(
+            Aabb::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 3.0, y: 3.0 }),
+            poly.bounding_box()
+        );
         // poly.print();
     }
 
@@ -210,6 +909,60 @@ mod test_polygon {
     }
 
     #[test]
+    fn test_try_new() {
+        let points = vec![Point2D { x: 0.0, y: 0.0 }, Point2D { x: 1.0, y: 1.0 }];
+        assert!(matches!(
+            Polygon2D::try_new(points),
+            Err(CgError::DegenerateGeometry(_))
+        ));
+
+        let points = vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D {
+                x: 1.0,
+                y: f64::NAN,
+            },
+            Point2D { x: 1.0, y: 1.0 },
+        ];
+        assert!(matches!(
+            Polygon2D::try_new(points),
+            Err(CgError::NanCoordinate)
+        ));
+
+        let points = vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+        ];
+        assert_eq!(4, Polygon2D::try_new(points).unwrap().points.len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let poly = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 0.0, y: 2.0 },
+        ]);
+        let json = serde_json::to_string(&poly).unwrap();
+        let parsed: Polygon2D = serde_json::from_str(&json).unwrap();
+        assert_eq!(poly.points, parsed.points);
+        assert_eq!(poly.segments, parsed.segments);
+        assert_eq!(poly.bounding_box(), parsed.bounding_box());
+
+        // The ring is closed and its segments are built from the points
+        let json = r#"{"points":[{"x":0,"y":0},{"x":2,"y":0},{"x":0,"y":2}],"segments":[]}"#;
+        let parsed: Polygon<i64> = serde_json::from_str(json).unwrap();
+        assert_eq!(4, parsed.points.len());
+        assert_eq!(3, parsed.segments.len());
+
+        let json = r#"{"points":[{"x":0,"y":0},{"x":2,"y":0}]}"#;
+        assert!(serde_json::from_str::<Polygon<i64>>(json).is_err());
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_inside() {
         let points = vec![
             Point2D { x: 0.0, y: 0.0 },
@@ -255,6 +1008,7 @@ mod test_polygon {
     }
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_should_work() {
         let points = vec![
             Point2D { x: 1.0, y: 1.0 },
@@ -265,11 +1019,90 @@ mod test_polygon {
 
         // This point is a point on the polygon line
         let p1: Point2D = Point2D { x: 1.0, y: 1.5 };
-        // This is wrong !!!!!
-        assert_ne!(true, poly.contains(&p1));
+        assert_eq!(true, poly.contains(&p1));
+        assert_eq!(PointLocation::OnBoundary, poly.locate(&p1));
     }
 
     #[test]
+    fn test_locate() {
+        let points = vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 1.0, y: 2.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 3.0, y: 3.0 },
+            Point2D { x: 3.0, y: 0.0 },
+        ];
+        let poly: Polygon2D = Polygon2D::new(points.clone());
+        let cases = [
+            (Point2D { x: 1.0, y: 1.0 }, PointLocation::OnBoundary),
+            (Point2D { x: 0.5, y: 0.5 }, PointLocation::OnBoundary),
+            (Point2D { x: 3.0, y: 1.5 }, PointLocation::OnBoundary),
+            (Point2D { x: 2.0, y: 1.0 }, PointLocation::Inside),
+            (Point2D { x: 1.1, y: 1.9 }, PointLocation::Inside),
+            (Point2D { x: 0.9, y: 1.1 }, PointLocation::Outside),
+            (Point2D { x: 1.0, y: 3.0 }, PointLocation::Outside),
+            // On the extension of the edge from (1,1) to (1,2)
+            (Point2D { x: 1.0, y: 0.5 }, PointLocation::Inside),
+            (Point2D { x: 4.0, y: 1.0 }, PointLocation::Outside),
+        ];
+        for (p, location) in cases {
+            assert_eq!(location, poly.locate(&p), "{p}");
+        }
+
+        // The ring is clock wise, its orientation changes only the sign of the winding number
+        let mut reversed = points;
+        reversed.reverse();
+        let reversed: Polygon2D = Polygon2D::new(reversed);
+        for (p, location) in cases {
+            assert_eq!(location, reversed.locate(&p), "{p}");
+        }
+        assert_eq!(-1, poly.winding_number(&Point2D { x: 2.0, y: 1.0 }));
+        assert_eq!(1, reversed.winding_number(&Point2D { x: 2.0, y: 1.0 }));
+
+        // Points exactly on an edge with a coordinate that is not representable
+        let triangle: Polygon2D = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 0.3, y: 0.0 },
+            Point2D { x: 0.0, y: 0.3 },
+        ]);
+        assert_eq!(
+            PointLocation::OnBoundary,
+            triangle.locate(&Point2D { x: 0.1, y: 0.0 })
+        );
+    }
+
+    #[test]
+    fn test_locate_within() {
+        let triangle: Polygon2D = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 0.0 },
+            Point2D { x: 0.0, y: 1.0 },
+        ]);
+        // A point that misses the diagonal edge by a rounding error
+        let p = Point2D {
+            x: 0.5,
+            y: 0.5 + 1e-12,
+        };
+        assert_eq!(PointLocation::Outside, triangle.locate(&p));
+        let tolerance = Tolerance::default();
+        assert_eq!(
+            PointLocation::OnBoundary,
+            triangle.locate_within(&p, &tolerance)
+        );
+        assert!(triangle.contains_within(&p, &tolerance));
+        assert!(triangle.contains_point_within(&p, &tolerance));
+        assert!(!triangle.contains_within(&p, &Tolerance::exact()));
+
+        let inside = Point2D { x: 0.2, y: 0.2 };
+        assert_eq!(
+            PointLocation::Inside,
+            triangle.locate_within(&inside, &tolerance)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_contains_point() {
         let points = vec![
             Point2D { x: 0.0, y: 0.0 },
@@ -316,6 +1149,174 @@ mod test_polygon {
         //     0 - 1 - 2 - 3
     }
 
+    #[test]
+    fn test_fill_rule() {
+        // The outer square is wound once and the inner square twice, the bridge between them is
+        // passed in both directions
+        let points = vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 4.0, y: 0.0 },
+            Point2D { x: 4.0, y: 4.0 },
+            Point2D { x: 0.0, y: 4.0 },
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 3.0, y: 1.0 },
+            Point2D { x: 3.0, y: 3.0 },
+            Point2D { x: 1.0, y: 3.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 0.0, y: 0.0 },
+        ];
+        let poly: Polygon2D = Polygon2D::new(points);
+        let center = Point2D { x: 2.0, y: 2.0 };
+        assert_eq!(2, poly.winding_number(&center));
+        assert_eq!(
+            PointLocation::Inside,
+            poly.locate_with(&center, FillRule::NonZero)
+        );
+        assert_eq!(
+            PointLocation::Outside,
+            poly.locate_with(&center, FillRule::EvenOdd)
+        );
+        assert!(!poly.contains_point(&center));
+        assert!(poly.contains_point_with(&center, FillRule::NonZero));
+        assert!(poly.contains_point(&Point2D { x: 0.5, y: 2.0 }));
+
+        assert_eq!(20.0, poly.calculate_area().abs());
+        assert_eq!(16.0, poly.area_with(FillRule::NonZero));
+        assert_eq!(12.0, poly.area_with(FillRule::EvenOdd));
+
+        // Both rules agree with the signed area of a simple polygon
+        let points = vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 1.0, y: 2.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 3.0, y: 3.0 },
+            Point2D { x: 3.0, y: 0.0 },
+        ];
+        let poly: Polygon2D = Polygon2D::new(points);
+        assert_eq!(
+            poly.calculate_area().abs(),
+            poly.area_with(FillRule::EvenOdd)
+        );
+        assert_eq!(
+            poly.calculate_area().abs(),
+            poly.area_with(FillRule::NonZero)
+        );
+
+        // Three edges on one line are crossed by a fourth one, whose crossings with them differ
+        // in the last bits
+        let points: Vec<Point2D> = [
+            (0.0, 1.0),
+            (1.0, 1.0),
+            (0.0, 0.0),
+            (4.0, 3.0),
+            (2.0, 0.0),
+            (1.0, 1.0),
+            (4.0, 1.0),
+        ]
+        .iter()
+        .map(|&(x, y)| Point2D {
+            x: x / 7.0,
+            y: y / 3.0,
+        })
+        .collect();
+        let poly: Polygon2D = Polygon2D::new(points);
+        assert!((poly.area_with(FillRule::NonZero) - 16.0 / 147.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_simple() {
+        let points = vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 1.0, y: 2.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 3.0, y: 3.0 },
+            Point2D { x: 3.0, y: 0.0 },
+        ];
+        let poly: Polygon2D = Polygon2D::new(points);
+        assert!(poly.is_simple());
+        assert!(poly.self_intersections().is_empty());
+
+        // The ring crosses itself between the edges 0 and 2
+        let bow_tie: Polygon2D = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 0.0, y: 2.0 },
+        ]);
+        assert!(!bow_tie.is_simple());
+        assert_eq!(1, bow_tie.self_intersections().len());
+
+        // The vertex (0,0) is visited twice, all four edges start there
+        let pinched: Polygon2D = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 1.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: -1.0 },
+            Point2D { x: 2.0, y: -2.0 },
+        ]);
+        assert!(!pinched.is_simple());
+        let intersections = pinched.self_intersections();
+        assert_eq!(1, intersections.len());
+        assert_eq!(Point2D { x: 0.0, y: 0.0 }, intersections[0].point);
+        assert_eq!(vec![0, 2, 3, 5], intersections[0].segments);
+
+        // A vertex on the interior of another edge
+        let touching: Polygon2D = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 4.0, y: 0.0 },
+            Point2D { x: 4.0, y: 2.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 0.0, y: 2.0 },
+        ]);
+        assert!(!touching.is_simple());
+
+        // A spike runs back on its own edge and a duplicate point is an edge of zero length
+        let spike: Polygon2D = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 3.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 2.0, y: 2.0 },
+        ]);
+        assert!(!spike.is_simple());
+        let duplicate: Polygon2D = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 2.0, y: 2.0 },
+        ]);
+        assert!(!duplicate.is_simple());
+    }
+
+    #[test]
+    fn test_simple_random() {
+        // A reproducible generator without dependencies
+        let mut state: u64 = 0x9E3779B97F4A7C15;
+        let mut next = |n: u64| splitmix64(&mut state) % n;
+
+        // Rings on a small grid touch themselves in vertices, edges and colinear parts
+        for _ in 0..2000 {
+            let n = 3 + next(6) as usize;
+            let points: Vec<Point2D> = (0..n)
+                .map(|_| Point2D {
+                    x: next(4) as f64,
+                    y: next(4) as f64,
+                })
+                .collect();
+            let poly = Polygon2D::new(points);
+            assert_eq!(
+                poly.self_intersections().is_empty(),
+                poly.is_simple(),
+                "{:?}",
+                poly.points
+            );
+        }
+    }
+
     #[test]
     fn test_area() {
         let points = vec![
@@ -338,5 +1339,95 @@ mod test_polygon {
 
         let poly = Polygon2D::new(points);
         assert_eq!(-1.0, poly.calculate_area());
+        assert_eq!(-1.0, poly.signed_area());
+        assert_eq!(1.0, poly.area());
+    }
+
+    #[test]
+    fn test_orientation() {
+        let points = vec![
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 1.0, y: 2.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 2.0, y: 1.0 },
+        ];
+        let cw = Polygon2D::new(points);
+        assert_eq!(Orientation::Clockwise, cw.orientation());
+        assert!(!cw.is_ccw());
+
+        let ccw = cw.to_ccw();
+        assert_eq!(Orientation::CounterClockwise, ccw.orientation());
+        assert!(ccw.is_ccw());
+        assert_eq!(1.0, ccw.signed_area());
+        assert_eq!(ccw.points, ccw.to_ccw().points);
+        assert_eq!(cw.points, ccw.to_cw().points);
+        assert_eq!(cw.points, ccw.reverse().points);
+        assert_eq!(cw.points.first(), cw.points.last());
+
+        let bow_tie = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 0.0, y: 2.0 },
+        ]);
+        assert_eq!(Orientation::Degenerate, bow_tie.orientation());
+        assert_eq!(bow_tie.points, bow_tie.to_ccw().points);
+        assert_eq!(bow_tie.points, bow_tie.to_cw().points);
+    }
+
+    #[cfg(feature = "exact")]
+    #[test]
+    fn test_area_exact() {
+        let points = vec![
+            Point2D { x: 0.1, y: 0.1 },
+            Point2D { x: 0.1, y: 0.3 },
+            Point2D { x: 0.3, y: 0.3 },
+            Point2D { x: 0.3, y: 0.1 },
+        ];
+
+        // The sides are not exactly 0.2 long, since 0.1 and 0.3 are rounded
+        let poly = Polygon2D::new(points);
+        let side = num_rational::BigRational::from_float(0.3).unwrap()
+            - num_rational::BigRational::from_float(0.1).unwrap();
+        assert_eq!(-(&side * &side), poly.calculate_area_exact().unwrap());
+    }
+
+    #[test]
+    fn test_generic() {
+        let points: Vec<Point<i64>> = vec![
+            Point { x: 0, y: 0 },
+            Point { x: 0, y: 4 },
+            Point { x: 4, y: 4 },
+            Point { x: 4, y: 0 },
+        ];
+        let poly = Polygon::new(points);
+        assert_eq!(-16, poly.calculate_area());
+        assert_eq!(16, poly.area());
+        assert_eq!(Orientation::Clockwise, poly.orientation());
+        assert_eq!(16, poly.to_ccw().signed_area());
+        assert!(poly.contains(&Point { x: 1, y: 2 }));
+        assert!(!poly.contains(&Point { x: 5, y: 2 }));
+        assert!(poly.contains_point(&Point { x: 3, y: 3 }));
+
+        // The area of the triangle is truncated, but not its orientation
+        let triangle: Polygon<i64> = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 1, y: 0 },
+            Point { x: 0, y: 1 },
+        ]);
+        assert_eq!(0, triangle.signed_area());
+        assert_eq!(Orientation::CounterClockwise, triangle.orientation());
+        assert!(triangle.is_ccw());
+        assert_eq!(triangle.points, triangle.to_ccw().points);
+        assert_eq!(triangle.reverse().points, triangle.to_cw().points);
+
+        let triangle: Polygon<i32> = Polygon::new(vec![
+            Point { x: 0, y: 0 },
+            Point { x: 0, y: 1 },
+            Point { x: 1, y: 0 },
+        ]);
+        assert_eq!(Orientation::Clockwise, triangle.orientation());
+        assert!(!triangle.is_ccw());
+        assert_eq!(triangle.reverse().points, triangle.to_ccw().points);
     }
 }
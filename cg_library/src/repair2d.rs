@@ -0,0 +1,703 @@
+//! Repair of polygons in a 2-Dimensional vector space.
+//!
+//! Provides the repair of invalid polygon rings for the computational geometry library
+//! [cg_library](crate), like the rings of imported maps.
+
+use crate::error::CgError;
+use crate::point2d::Point2D;
+use crate::polygon2d::{FillRule, PointLocation, Polygon2D};
+use crate::tolerance::Tolerance;
+use crate::tools2d::orient2d;
+use crate::util::intersection::IntersectionKind;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A change that the [repair](Polygon2D::repair) of a polygon made.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RepairAction {
+    /// A point equal to its predecessor was removed, which was an edge of zero length.
+    RemovedDuplicate(Point2D),
+
+    /// A point on the line through its neighbors was removed, like the tip of a spike.
+    RemovedColinear(Point2D),
+
+    /// The ring was split into two rings at a point where it touches or crosses itself.
+    SplitAt(Point2D),
+
+    /// A ring was reversed to be counter clock wise, which is the polygon at the given index of
+    /// the [repaired polygons](RepairReport::polygons).
+    Reversed(usize),
+
+    /// A ring was reversed to be clock wise, which is the hole at the given index of the
+    /// [repaired holes](RepairReport::holes).
+    ReversedHole(usize),
+
+    /// A ring was dissolved, since the regions on both of its sides are filled alike, like the
+    /// center of a pentagram under the [non-zero](FillRule::NonZero) rule.
+    Dissolved,
+
+    /// A ring with less than three points left was dropped, since it has no area.
+    DroppedDegenerate,
+}
+
+/// This trait allows an action to be displayed as a sentence.
+impl fmt::Display for RepairAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RepairAction::RemovedDuplicate(p) => write!(f, "Removed duplicate point {p}"),
+            RepairAction::RemovedColinear(p) => write!(f, "Removed colinear point {p}"),
+            RepairAction::SplitAt(p) => write!(f, "Split ring at {p}"),
+            RepairAction::Reversed(i) => write!(f, "Reversed ring {i}"),
+            RepairAction::ReversedHole(i) => write!(f, "Reversed hole {i}"),
+            RepairAction::Dissolved => write!(f, "Dissolved ring inside of a region"),
+            RepairAction::DroppedDegenerate => write!(f, "Dropped degenerate ring"),
+        }
+    }
+}
+
+/// The result of the [repair](Polygon2D::repair) of a polygon.
+#[derive(Debug, Clone)]
+pub struct RepairReport {
+    /// The simple counter clock wise polygons the ring was repaired into.
+    pub polygons: Vec<Polygon2D>,
+
+    /// The simple clock wise holes inside of the [polygons](RepairReport::polygons), whose area
+    /// is not filled.
+    pub holes: Vec<Polygon2D>,
+
+    /// Every change in the order it was made.
+    pub actions: Vec<RepairAction>,
+}
+
+impl RepairReport {
+    /// Returns `true` iff the polygon was valid already.
+    pub fn is_unchanged(&self) -> bool {
+        self.actions.is_empty()
+    }
+}
+
+/// A vertex of a ring during the repair.
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    /// The point of the vertex.
+    point: Point2D,
+
+    /// `true` iff the point is a vertex of the repaired polygon, and not a self-intersection that
+    /// was inserted into its edges.
+    input: bool,
+}
+
+/// Returns the points of a ring.
+fn points_of(ring: &[Vertex]) -> Vec<Point2D> {
+    ring.iter().map(|v| v.point).collect()
+}
+
+/// Returns the ring without duplicate and colinear points.
+///
+/// Removing a point can make its neighbors colinear, so the ring is checked until nothing
+/// changes. The points are compared with the exact predicate [orient2d]. Only the removal of
+/// vertices of the repaired polygon is recorded as an action, inserted self-intersections are
+/// removed silently.
+fn clean(mut ring: Vec<Vertex>, actions: &mut Vec<RepairAction>) -> Vec<Vertex> {
+    let mut changed = true;
+    while changed && ring.len() >= 3 {
+        changed = false;
+        let mut i = 0;
+        while i < ring.len() && ring.len() >= 3 {
+            let n = ring.len();
+            let (prev, next) = (ring[(i + n - 1) % n].point, ring[(i + 1) % n].point);
+            let action = if ring[i].point == prev || ring[i].point == next {
+                RepairAction::RemovedDuplicate
+            } else if orient2d(&prev, &ring[i].point, &next) == 0.0 {
+                RepairAction::RemovedColinear
+            } else {
+                i += 1;
+                continue;
+            };
+            let removed = ring.remove(i);
+            if removed.input {
+                actions.push(action(removed.point));
+            }
+            changed = true;
+        }
+    }
+    ring
+}
+
+/// Returns the ring with every self-intersection point inserted into the edges it lies on.
+///
+/// Afterwards the ring touches itself only in vertices. The points of the
+/// [self-intersections](Polygon2D::self_intersections) are not snapped, so they lie as close to
+/// their edges as floating point coordinates allow.
+fn node(ring: &[Vertex], polygon: &Polygon2D) -> Vec<Vertex> {
+    let mut inserted: Vec<Vec<Point2D>> = vec![Vec::new(); ring.len()];
+    for intersection in polygon.self_intersections() {
+        let points = match intersection.kind {
+            IntersectionKind::Point => vec![intersection.point],
+            IntersectionKind::Overlap(overlap) => vec![overlap.p1, overlap.p2],
+        };
+        for edge in intersection.segments {
+            inserted[edge].extend(points.iter().copied());
+        }
+    }
+
+    let mut noded: Vec<Vertex> = Vec::new();
+    for (i, points) in inserted.iter_mut().enumerate() {
+        let (a, b) = (ring[i].point, ring[(i + 1) % ring.len()].point);
+        points.retain(|p| *p != a && *p != b);
+        points.sort_by(|p, q| {
+            let (dp, dq) = ((*p - a).norm_squared(), (*q - a).norm_squared());
+            dp.total_cmp(&dq)
+        });
+        points.dedup();
+        noded.push(ring[i]);
+        noded.extend(points.iter().map(|&point| Vertex {
+            point,
+            input: false,
+        }));
+    }
+    noded
+}
+
+/// Appends the rings a cleaned ring is split into until every ring is simple.
+///
+/// A part of a split ring can still touch itself, like at a crossing point that was rounded off
+/// its edges, so every part is checked again. A part that is not split any further results in a
+/// [degenerate geometry error](CgError::DegenerateGeometry), since its area can not be resolved.
+fn resolve(
+    ring: Vec<Vertex>,
+    rings: &mut Vec<Vec<Vertex>>,
+    actions: &mut Vec<RepairAction>,
+) -> Result<(), CgError> {
+    if ring.len() < 3 {
+        rings.push(ring);
+        return Ok(());
+    }
+    let polygon = Polygon2D::from_ring(&points_of(&ring));
+    if polygon.is_simple() {
+        rings.push(ring);
+        return Ok(());
+    }
+
+    let parts = split(node(&ring, &polygon), actions);
+    if parts.len() == 1 {
+        return Err(CgError::DegenerateGeometry(String::from(
+            "the ring touches itself at a point that can not be inserted into its edges",
+        )));
+    }
+    for part in parts {
+        let part = clean(part, actions);
+        resolve(part, rings, actions)?;
+    }
+    Ok(())
+}
+
+/// Returns the rings of a noded ring split at every vertex it visits twice.
+///
+/// At such a vertex the ends of the edges are sorted around it and every incoming edge is
+/// continued by an outgoing one, such that no two pairs cross. The rings that follow these pairs
+/// only touch each other, so that any two of them either lie inside of each other or apart. A
+/// ring that still visits a vertex twice is cut at it, which does not make rings cross either.
+/// Both rings keep the point as a vertex of the repaired polygon, if one of the visits is such a
+/// vertex.
+fn split(noded: Vec<Vertex>, actions: &mut Vec<RepairAction>) -> Vec<Vec<Vertex>> {
+    let n = noded.len();
+    let mut visits: BTreeMap<Point2D, Vec<usize>> = BTreeMap::new();
+    for (i, v) in noded.iter().enumerate() {
+        visits.entry(v.point).or_default().push(i);
+    }
+
+    // The edge `i` runs from vertex `i` to the next one, which it is continued by at first
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let mut input: Vec<bool> = noded.iter().map(|v| v.input).collect();
+    for (i, v) in noded.iter().enumerate() {
+        let indices = &visits[&v.point];
+        if indices.len() < 2 || indices[0] != i {
+            continue;
+        }
+        actions.push(RepairAction::SplitAt(v.point));
+        let visited_input = indices.iter().any(|&j| noded[j].input);
+        for &j in indices {
+            input[j] = visited_input;
+        }
+        for (incoming, outgoing) in pair_ends(&noded, indices) {
+            next[incoming] = outgoing;
+        }
+    }
+
+    let mut rings: Vec<Vec<Vertex>> = Vec::new();
+    let mut visited = vec![false; n];
+    for first in 0..n {
+        let mut path: Vec<Vertex> = Vec::new();
+        let mut i = first;
+        while !visited[i] {
+            visited[i] = true;
+            let v = Vertex {
+                point: noded[i].point,
+                input: input[i],
+            };
+            if let Some(start) = path.iter().position(|u| u.point == v.point) {
+                rings.push(path.split_off(start));
+            }
+            path.push(v);
+            i = next[i];
+        }
+        if !path.is_empty() {
+            rings.push(path);
+        }
+    }
+    rings
+}
+
+/// Returns the pairs of an incoming and an outgoing edge at a vertex of a noded ring, which the
+/// ring visits at the given indices, see [split].
+///
+/// The ends are sorted counter clock wise around the vertex with the exact predicate [orient2d].
+/// Like parentheses every end is paired with the previous unpaired end of the other kind, so
+/// that the pairs do not cross.
+fn pair_ends(noded: &[Vertex], indices: &[usize]) -> Vec<(usize, usize)> {
+    let n = noded.len();
+    let center = noded[indices[0]].point;
+
+    // The incoming edge `i - 1` ends and the outgoing edge `i` starts at the visit `i`
+    let mut ends: Vec<(Point2D, bool, usize)> = Vec::new();
+    for &i in indices {
+        let previous = (i + n - 1) % n;
+        ends.push((noded[previous].point, false, previous));
+        ends.push((noded[(i + 1) % n].point, true, i));
+    }
+    // Edges on top of each other are ordered as if every edge was shifted by its index to the
+    // left of its line from the lower to the greater endpoint, which is the same at both ends
+    let half = |p: &Point2D| p.y < center.y || (p.y == center.y && p.x < center.x);
+    let shift = |p: &Point2D, edge: usize| {
+        if center < *p {
+            edge as isize
+        } else {
+            -(edge as isize)
+        }
+    };
+    ends.sort_by(|(p, _, e), (q, _, f)| {
+        half(p)
+            .cmp(&half(q))
+            .then_with(|| match orient2d(&center, p, q) {
+                o if o > 0.0 => Ordering::Less,
+                o if o < 0.0 => Ordering::Greater,
+                _ => Ordering::Equal,
+            })
+            .then_with(|| shift(p, *e).cmp(&shift(q, *f)))
+    });
+
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    let mut open: Vec<(bool, usize)> = Vec::new();
+    for (_, outgoing, edge) in ends {
+        match open.last() {
+            Some(&(other, previous)) if other != outgoing => {
+                open.pop();
+                pairs.push(if outgoing {
+                    (previous, edge)
+                } else {
+                    (edge, previous)
+                });
+            }
+            _ => open.push((outgoing, edge)),
+        }
+    }
+    pairs
+}
+
+/// Returns a point inside of a simple polygon, that lies far from its boundary.
+///
+/// A horizontal line runs through the middle of the widest gap between the y-coordinates of the
+/// vertices, so it does not pass a vertex. The point is the middle of the widest part of the line
+/// inside of the polygon. Polygons too thin for such a line return their first vertex.
+fn interior_point(polygon: &Polygon2D) -> Point2D {
+    let mut ys: Vec<f64> = polygon.points.iter().map(|p| p.y).collect();
+    ys.sort_by(f64::total_cmp);
+    ys.dedup();
+    let Some(gap) = ys
+        .windows(2)
+        .max_by(|a, b| (a[1] - a[0]).total_cmp(&(b[1] - b[0])))
+    else {
+        return polygon.points[0];
+    };
+    let y = (gap[0] + gap[1]) / 2.0;
+
+    let mut xs: Vec<f64> = polygon
+        .points
+        .windows(2)
+        .filter(|edge| (edge[0].y < y) != (edge[1].y < y))
+        .map(|edge| {
+            let (a, b) = (edge[0], edge[1]);
+            a.x + (y - a.y) * (b.x - a.x) / (b.y - a.y)
+        })
+        .collect();
+    xs.sort_by(f64::total_cmp);
+    xs.chunks_exact(2)
+        .max_by(|a, b| (a[1] - a[0]).total_cmp(&(b[1] - b[0])))
+        .map_or(polygon.points[0], |inside| Point2D {
+            x: (inside[0] + inside[1]) / 2.0,
+            y,
+        })
+}
+
+impl Polygon2D {
+    /// Returns the polygon repaired into valid simple polygons and holes together with every
+    /// change, where the regions of a ring that crosses itself are filled under the
+    /// [non-zero](FillRule::NonZero) rule, see [repair_with](Polygon2D::repair_with).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cg_library::point2d::Point2D;
+    /// use cg_library::polygon2d::Polygon2D;
+    /// use cg_library::repair2d::RepairAction;
+    ///
+    /// let bow_tie = Polygon2D::new(vec![
+    ///     Point2D { x: 0.0, y: 0.0 },
+    ///     Point2D { x: 2.0, y: 2.0 },
+    ///     Point2D { x: 2.0, y: 0.0 },
+    ///     Point2D { x: 0.0, y: 2.0 },
+    /// ]);
+    /// let report = bow_tie.repair().unwrap();
+    /// assert_eq!(2, report.polygons.len());
+    /// assert!(report.polygons.iter().all(|p| p.is_simple()));
+    /// assert_eq!(RepairAction::SplitAt(Point2D { x: 1.0, y: 1.0 }), report.actions[0]);
+    /// ```
+    pub fn repair(&self) -> Result<RepairReport, CgError> {
+        self.repair_with(FillRule::NonZero)
+    }
+
+    /// Returns the polygon repaired into valid simple polygons and holes together with every
+    /// change, where the regions of a ring that crosses itself are filled under a fill rule.
+    ///
+    /// The repair takes the following steps:
+    /// 1. Duplicate consecutive points and colinear points, like the tips of spikes, are removed.
+    /// 2. If the ring is not [simple](Polygon2D::is_simple), its self-intersections are inserted as
+    ///    points and the ring is split at every point it visits twice. The parts are cleaned like
+    ///    in the first step and split again until they are simple.
+    /// 3. Rings with less than three points are dropped.
+    /// 4. The split rings only touch each other, so they are nested. Every ring keeps its
+    ///    winding, which adds up to the [winding number](Polygon2D::winding_number) of the
+    ///    regions inside of it. A ring with a filled region on its inside only is a polygon, which
+    ///    is reversed to be counter clock wise. A ring with a filled region on its outside only is
+    ///    a hole, which is reversed to be clock wise. Other rings are dissolved.
+    ///
+    /// The area of the polygons minus the area of the holes is the
+    /// [area](Polygon2D::area_with) of the ring under the rule. Only changes to the vertices of
+    /// the polygon are recorded, self-intersections that are inserted and removed again are not.
+    ///
+    /// # Errors
+    /// A crossing that lies closer to a vertex or another crossing than floating point
+    /// coordinates can resolve is rounded, so in rare cases a part can not be split any further
+    /// or the parts cross each other. Instead of changing the area a
+    /// [degenerate geometry error](CgError::DegenerateGeometry) is returned, if a part is not
+    /// simple or the repaired area differs from the area of the ring.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cg_library::point2d::Point2D;
+    /// use cg_library::polygon2d::{FillRule, Polygon2D};
+    ///
+    /// // The ring runs around the center of the pentagram twice
+    /// let pentagram = Polygon2D::new(vec![
+    ///     Point2D { x: 0.0, y: 3.0 },
+    ///     Point2D { x: 2.0, y: -3.0 },
+    ///     Point2D { x: -3.0, y: 1.0 },
+    ///     Point2D { x: 3.0, y: 1.0 },
+    ///     Point2D { x: -2.0, y: -3.0 },
+    /// ]);
+    /// let report = pentagram.repair_with(FillRule::EvenOdd).unwrap();
+    /// assert_eq!(1, report.polygons.len());
+    /// assert_eq!(1, report.holes.len());
+    /// ```
+    pub fn repair_with(&self, rule: FillRule) -> Result<RepairReport, CgError> {
+        let mut actions: Vec<RepairAction> = Vec::new();
+        let ring = self.points[..self.points.len() - 1]
+            .iter()
+            .map(|&point| Vertex { point, input: true })
+            .collect();
+        let ring = clean(ring, &mut actions);
+
+        let mut rings: Vec<Vec<Vertex>> = Vec::new();
+        resolve(ring, &mut rings, &mut actions)?;
+
+        let mut parts: Vec<Polygon2D> = Vec::new();
+        for ring in rings {
+            if ring.len() < 3 {
+                actions.push(RepairAction::DroppedDegenerate);
+                continue;
+            }
+            parts.push(Polygon2D::from_ring(&points_of(&ring)));
+        }
+
+        // The parent of a ring is the smallest one it lies inside of, the rings are sorted by
+        // their area, so that parents come first. Since the rings do not cross, a point inside
+        // of a ring decides whether it lies inside of a greater one.
+        let mut order: Vec<usize> = (0..parts.len()).collect();
+        order.sort_by(|&i, &j| parts[j].area().total_cmp(&parts[i].area()).then(i.cmp(&j)));
+        let mut winding: Vec<i32> = vec![0; parts.len()];
+        let mut outside: Vec<i32> = vec![0; parts.len()];
+        for (k, &i) in order.iter().enumerate() {
+            let p = interior_point(&parts[i]);
+            let parent = order[..k]
+                .iter()
+                .rev()
+                .find(|&&j| parts[j].locate(&p) == PointLocation::Inside);
+            outside[i] = parent.map_or(0, |&j| winding[j]);
+            winding[i] = outside[i] + if parts[i].is_ccw() { 1 } else { -1 };
+        }
+
+        let mut polygons: Vec<Polygon2D> = Vec::new();
+        let mut holes: Vec<Polygon2D> = Vec::new();
+        for (i, part) in parts.into_iter().enumerate() {
+            match (rule.is_filled(winding[i]), rule.is_filled(outside[i])) {
+                (true, false) => {
+                    if !part.is_ccw() {
+                        actions.push(RepairAction::Reversed(polygons.len()));
+                    }
+                    polygons.push(part.to_ccw());
+                }
+                (false, true) => {
+                    if part.is_ccw() {
+                        actions.push(RepairAction::ReversedHole(holes.len()));
+                    }
+                    holes.push(part.to_cw());
+                }
+                _ => actions.push(RepairAction::Dissolved),
+            }
+        }
+
+        // A crossing that was rounded into a different part changes the area
+        let area = polygons.iter().map(|p| p.area()).sum::<f64>()
+            - holes.iter().map(|p| p.area()).sum::<f64>();
+        let expected = self.area_with(rule);
+        let tolerance = Tolerance {
+            absolute: 0.0,
+            relative: 1e-9,
+            digits: None,
+        };
+        if (area - expected).abs() > tolerance.bound(self.bounding_box().area()) {
+            return Err(CgError::DegenerateGeometry(format!(
+                "the repaired area {area} differs from the area {expected} of the ring"
+            )));
+        }
+        Ok(RepairReport {
+            polygons,
+            holes,
+            actions,
+        })
+    }
+
+    /// Returns the polygon of a ring without the closing point.
+    fn from_ring(ring: &[Point2D]) -> Polygon2D {
+        let mut points = ring.to_vec();
+        points.push(ring[0]);
+        Polygon2D::new(points)
+    }
+}
+
+#[cfg(test)]
+mod test_repair2d {
+    use super::*;
+
+    #[test]
+    fn test_clean() {
+        // A duplicate point, a point in the middle of an edge and a spike
+        let poly = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 4.0, y: 0.0 },
+            Point2D { x: 4.0, y: 4.0 },
+            Point2D { x: 4.0, y: 6.0 },
+            Point2D { x: 4.0, y: 4.0 },
+            Point2D { x: 0.0, y: 4.0 },
+            Point2D { x: 0.0, y: 0.0 },
+        ]);
+        let report = poly.repair().unwrap();
+        assert_eq!(
+            vec![
+                RepairAction::RemovedDuplicate(Point2D { x: 0.0, y: 0.0 }),
+                RepairAction::RemovedColinear(Point2D { x: 2.0, y: 0.0 }),
+                RepairAction::RemovedColinear(Point2D { x: 4.0, y: 4.0 }),
+                RepairAction::RemovedColinear(Point2D { x: 4.0, y: 6.0 }),
+            ],
+            report.actions
+        );
+        assert_eq!(1, report.polygons.len());
+        assert_eq!(5, report.polygons[0].points.len());
+        assert_eq!(16.0, report.polygons[0].signed_area());
+        assert!(report.polygons[0].is_simple());
+    }
+
+    #[test]
+    fn test_orientation() {
+        let square = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 0.0, y: 1.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 1.0, y: 0.0 },
+        ]);
+        let report = square.repair().unwrap();
+        assert_eq!(vec![RepairAction::Reversed(0)], report.actions);
+        assert!(report.polygons[0].is_ccw());
+        assert_eq!(1.0, report.polygons[0].signed_area());
+
+        let report = report.polygons[0].repair().unwrap();
+        assert!(report.is_unchanged());
+    }
+
+    #[test]
+    fn test_split() {
+        // The ring touches itself at (2,2) and crosses itself at (3,3)
+        let poly = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 4.0, y: 4.0 },
+            Point2D { x: 4.0, y: 2.0 },
+            Point2D { x: 2.0, y: 4.0 },
+            Point2D { x: 2.0, y: 2.0 },
+            Point2D { x: 0.0, y: 2.0 },
+        ]);
+        assert!(!poly.is_simple());
+        let report = poly.repair().unwrap();
+        assert_eq!(3, report.polygons.len());
+        assert!(report.polygons.iter().all(|p| p.is_simple()));
+        assert!(report.polygons.iter().all(|p| p.is_ccw()));
+        assert!(report
+            .actions
+            .contains(&RepairAction::SplitAt(Point2D { x: 2.0, y: 2.0 })));
+        assert!(report
+            .actions
+            .contains(&RepairAction::SplitAt(Point2D { x: 3.0, y: 3.0 })));
+
+        let area: f64 = report.polygons.iter().map(|p| p.area()).sum();
+        assert_eq!(6.0, area);
+    }
+
+    #[test]
+    fn test_inserted() {
+        // The ring crosses itself at (2.5,2.5), (1.5,1.5) and (4/3,1), the parts only touch each
+        // other along the edge between the last two crossings
+        let poly = Polygon2D::new(vec![
+            Point2D { x: 4.0, y: 4.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 4.0, y: 1.0 },
+            Point2D { x: 2.0, y: 3.0 },
+            Point2D { x: 1.0, y: 0.0 },
+            Point2D { x: 0.0, y: 3.0 },
+            Point2D { x: 1.0, y: 3.0 },
+        ]);
+        let report = poly.repair().unwrap();
+        assert_eq!(
+            vec![
+                RepairAction::SplitAt(Point2D { x: 2.5, y: 2.5 }),
+                RepairAction::SplitAt(Point2D { x: 1.5, y: 1.5 }),
+                RepairAction::SplitAt(Point2D {
+                    x: 4.0 / 3.0,
+                    y: 1.0
+                }),
+                RepairAction::Reversed(0),
+            ],
+            report.actions
+        );
+        assert_eq!(2, report.polygons.len());
+        assert!(report.polygons.iter().all(|p| p.is_simple()));
+        assert!(report.polygons.iter().all(|p| p.is_ccw()));
+        let area: f64 = report.polygons.iter().map(|p| p.area()).sum();
+        assert!((poly.area_with(FillRule::NonZero) - area).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fill_rule() {
+        // The loop of the pinched ring runs clock wise inside of the square, so it is a hole
+        let pinched = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 1.0, y: 2.0 },
+            Point2D { x: 3.0, y: 2.0 },
+            Point2D { x: 3.0, y: 1.0 },
+            Point2D { x: 2.0, y: 0.0 },
+            Point2D { x: 4.0, y: 0.0 },
+            Point2D { x: 4.0, y: 4.0 },
+            Point2D { x: 0.0, y: 4.0 },
+        ]);
+        let report = pinched.repair().unwrap();
+        assert_eq!(1, report.polygons.len());
+        assert_eq!(1, report.holes.len());
+        assert_eq!(16.0, report.polygons[0].signed_area());
+        assert_eq!(-3.0, report.holes[0].signed_area());
+
+        // The center of the pentagram is filled twice, which only the non-zero rule fills
+        let pentagram = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 3.0 },
+            Point2D { x: 2.0, y: -3.0 },
+            Point2D { x: -3.0, y: 1.0 },
+            Point2D { x: 3.0, y: 1.0 },
+            Point2D { x: -2.0, y: -3.0 },
+        ]);
+        let report = pentagram.repair().unwrap();
+        assert_eq!(Some(&RepairAction::Dissolved), report.actions.last());
+        assert!(report.holes.is_empty());
+
+        for poly in [pinched, pentagram] {
+            for rule in [FillRule::NonZero, FillRule::EvenOdd] {
+                let report = poly.repair_with(rule).unwrap();
+                assert!(report.polygons.iter().all(|p| p.is_simple() && p.is_ccw()));
+                assert!(report.holes.iter().all(|p| p.is_simple() && !p.is_ccw()));
+                let area = report.polygons.iter().map(|p| p.area()).sum::<f64>()
+                    - report.holes.iter().map(|p| p.area()).sum::<f64>();
+                assert!((poly.area_with(rule) - area).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsnapped() {
+        // The edges cross at (1,2/3), which the sweep line snaps to 9 decimal places
+        let poly = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 3.0, y: 2.0 },
+            Point2D { x: 3.0, y: 0.0 },
+            Point2D { x: 0.0, y: 1.0 },
+        ]);
+        let report = poly.repair().unwrap();
+        assert_eq!(2, report.polygons.len());
+        assert!(report.polygons.iter().all(|p| p.is_simple()));
+        match report.actions[0] {
+            RepairAction::SplitAt(p) => {
+                assert!((p.y - 2.0 / 3.0).abs() < 1e-15);
+                assert_ne!(
+                    Point2D {
+                        x: 1.0,
+                        y: 0.666666667
+                    },
+                    p
+                );
+            }
+            action => panic!("{action}"),
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // All points lie on one line
+        let poly = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 3.0, y: 3.0 },
+        ]);
+        let report = poly.repair().unwrap();
+        assert!(report.polygons.is_empty());
+        assert_eq!(
+            Some(&RepairAction::DroppedDegenerate),
+            report.actions.last()
+        );
+        assert_eq!("Dropped degenerate ring", report.actions[1].to_string());
+    }
+}
@@ -0,0 +1,162 @@
+//! Scalar types of the coordinates in a 2-Dimensional vector space.
+//!
+//! Provides the scalar trait, that all geometry types of the computational geometry library
+//! [cg_library](crate) are generic over.
+
+use crate::point2d::Point;
+use crate::tools2d::{ccw, orient2d};
+use num_traits::Signed;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A scalar that can be used as the coordinate of a [Point](crate::point2d::Point).
+///
+/// It is implemented for `f32`, `f64`, `i32`, `i64` and `i128`. With the cargo feature `exact`
+/// it is implemented for the rationals `Ratio<i64>` and `Ratio<i128>` as well. Integer scalars
+/// are exact as long as the calculations do not overflow. The orientation of `i32` and `i64`
+/// points is evaluated in the next wider integer, which does not overflow for coordinates within
+/// half of their range. Points that need a division, like intersection points, are only
+/// calculated for a [field](Field).
+///
+/// Every scalar is totally ordered and hashable, so that points and segments can be keys of sorted
+/// and hashed collections. Floats are canonicalized for this, `-0.0` equals `0.0` and all NaNs
+/// equal each other and are greater than every number.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::linesegment2d::LineSegment;
+/// use cg_library::point2d::Point;
+///
+/// let s1 = LineSegment::new(Point { x: 0, y: 0 }, Point { x: 4, y: 4 });
+/// let s2 = LineSegment::new(Point { x: 0, y: 3 }, Point { x: 3, y: 0 });
+/// assert!(s1.is_intersecting(&s2));
+/// ```
+pub trait Scalar: Signed + Copy + PartialOrd + fmt::Debug + fmt::Display {
+    /// Returns the counter clock wise value for three points.
+    ///
+    /// Only the sign of the value is used by the algorithms, except for the intersection points
+    /// of a [field](Field). It is the plain [ccw](crate::tools2d::ccw) value by default.
+    fn orientation(p: &Point<Self>, q: &Point<Self>, r: &Point<Self>) -> Self {
+        ccw(p, q, r)
+    }
+
+    /// Returns the total order of two values.
+    ///
+    /// It is the partial order by default, which is total for all scalars except floats.
+    fn canonical_cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap()
+    }
+
+    /// Feeds the value into a hasher, so that values that are equal in the
+    /// [total order](Scalar::canonical_cmp) have the same hash.
+    fn canonical_hash<H: Hasher>(&self, state: &mut H);
+}
+
+/// A scalar with a division that inverts the multiplication, which are the floats and the
+/// rationals.
+///
+/// The point where two segments cross divides them in the ratio of two
+/// [orientations](Scalar::orientation). Integers would truncate this ratio and move the point off
+/// both segments, so [intersects](crate::linesegment2d::LineSegment::intersects) is only
+/// implemented for fields.
+pub trait Field: Scalar {}
+
+impl Field for f32 {}
+
+impl Field for f64 {}
+
+#[cfg(feature = "exact")]
+impl Field for num_rational::Ratio<i64> {}
+
+#[cfg(feature = "exact")]
+impl Field for num_rational::Ratio<i128> {}
+
+/// Returns the value with `0.0` for `-0.0` and one NaN for all NaNs.
+fn canonical<F: num_traits::Float>(value: F) -> F {
+    if value.is_nan() {
+        F::nan()
+    } else if value == F::zero() {
+        F::zero()
+    } else {
+        value
+    }
+}
+
+impl Scalar for f32 {
+    fn canonical_cmp(&self, other: &f32) -> Ordering {
+        canonical(*self).total_cmp(&canonical(*other))
+    }
+
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) {
+        canonical(*self).to_bits().hash(state);
+    }
+}
+
+/// The orientation of `f64` points is evaluated with the exact predicate
+/// [orient2d](crate::tools2d::orient2d).
+impl Scalar for f64 {
+    fn orientation(p: &Point<f64>, q: &Point<f64>, r: &Point<f64>) -> f64 {
+        orient2d(p, q, r)
+    }
+
+    fn canonical_cmp(&self, other: &f64) -> Ordering {
+        canonical(*self).total_cmp(&canonical(*other))
+    }
+
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) {
+        canonical(*self).to_bits().hash(state);
+    }
+}
+
+/// The orientation of `i32` points is evaluated in `i64` and only its sign is returned.
+impl Scalar for i32 {
+    fn orientation(p: &Point<i32>, q: &Point<i32>, r: &Point<i32>) -> i32 {
+        let wide = |p: &Point<i32>| Point {
+            x: i64::from(p.x),
+            y: i64::from(p.y),
+        };
+        ccw(&wide(p), &wide(q), &wide(r)).signum() as i32
+    }
+
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) {
+        self.hash(state);
+    }
+}
+
+/// The orientation of `i64` points is evaluated in `i128` and only its sign is returned.
+impl Scalar for i64 {
+    fn orientation(p: &Point<i64>, q: &Point<i64>, r: &Point<i64>) -> i64 {
+        let wide = |p: &Point<i64>| Point {
+            x: i128::from(p.x),
+            y: i128::from(p.y),
+        };
+        ccw(&wide(p), &wide(q), &wide(r)).signum() as i64
+    }
+
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) {
+        self.hash(state);
+    }
+}
+
+impl Scalar for i128 {
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) {
+        self.hash(state);
+    }
+}
+
+/// Rationals are always reduced, so equal values have equal hashes.
+#[cfg(feature = "exact")]
+impl Scalar for num_rational::Ratio<i64> {
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) {
+        self.hash(state);
+    }
+}
+
+#[cfg(feature = "exact")]
+impl Scalar for num_rational::Ratio<i128> {
+    fn canonical_hash<H: Hasher>(&self, state: &mut H) {
+        self.hash(state);
+    }
+}
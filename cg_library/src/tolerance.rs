@@ -0,0 +1,165 @@
+//! Numeric tolerance in a 2-Dimensional vector space.
+//!
+//! Provides the tolerance policy of the computational geometry library [cg_library](crate), which
+//! decides when floating point values are considered equal.
+
+use crate::point2d::Point2D;
+use crate::scalar::Scalar;
+use crate::tools2d::{orient2d, round_to_decimal_places};
+use num_traits::Float;
+use std::cmp::Ordering;
+
+/// The tolerance of floating point comparisons.
+///
+/// Two values are equal if their difference is at most the absolute epsilon, or at most the
+/// relative epsilon times the larger magnitude of both. The absolute epsilon suits coordinates
+/// of unit scale, the relative epsilon suits large coordinates. Intersection points of the
+/// [bently ottmann](crate::tools2d::bently_ottmann) algorithm are snapped to a number of decimal
+/// places, so that the same point of different pairs of segments matches.
+///
+/// The [default](Tolerance::default) is an absolute epsilon of `1e-8` without a relative one and
+/// snapping to 9 decimal places.
+///
+/// # Example
+///
+/// This creates a tolerance for coordinates in the range of millions:
+/// ```
+/// use cg_library::tolerance::Tolerance;
+/// let tolerance = Tolerance {
+///     relative: 1e-12,
+///     digits: Some(3),
+///     ..Default::default()
+/// };
+/// assert!(tolerance.is_equal(1e6, 1e6 + 1e-7));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tolerance {
+    /// The absolute epsilon.
+    pub absolute: f64,
+
+    /// The relative epsilon, which is scaled by the magnitude of the compared values.
+    pub relative: f64,
+
+    /// The number of decimal places intersection points are snapped to, `None` keeps them.
+    pub digits: Option<u32>,
+}
+
+/// This trait returns an absolute epsilon of `1e-8` and snapping to 9 decimal places, which suits
+/// coordinates of unit scale.
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance {
+            absolute: 1e-8,
+            relative: 0.0,
+            digits: Some(9),
+        }
+    }
+}
+
+impl Tolerance {
+    /// Returns the tolerance that allows no error at all and does not snap points.
+    pub fn exact() -> Tolerance {
+        Tolerance {
+            absolute: 0.0,
+            relative: 0.0,
+            digits: None,
+        }
+    }
+
+    /// Returns the allowed error for values of a magnitude.
+    pub fn bound<T: Float>(&self, magnitude: T) -> T {
+        let absolute = T::from(self.absolute).unwrap();
+        let relative = T::from(self.relative).unwrap();
+        absolute.max(relative * magnitude.abs())
+    }
+
+    /// Returns `true` iff two values are equal within the tolerance.
+    pub fn is_equal<T: Float>(&self, a: T, b: T) -> bool {
+        (a - b).abs() <= self.bound(a.abs().max(b.abs()))
+    }
+
+    /// Returns `true` iff a value is zero within the absolute epsilon.
+    pub fn is_zero<T: Float>(&self, value: T) -> bool {
+        value.abs() <= T::from(self.absolute).unwrap()
+    }
+
+    /// Returns the point snapped to the decimal places of the tolerance.
+    pub fn snap(&self, p: Point2D) -> Point2D {
+        match self.digits {
+            Some(digits) => Point2D {
+                x: round_to_decimal_places(p.x, digits),
+                y: round_to_decimal_places(p.y, digits),
+            },
+            None => p,
+        }
+    }
+
+    /// Returns the orientation of the point `c` to the line from `a` to `b`.
+    ///
+    /// The sign is taken from the exact predicate [orient2d], but a point whose distance to the
+    /// line lies within the tolerance counts as colinear. The result is `Greater` for a counter
+    /// clock wise and `Less` for a clock wise orientation.
+    pub fn orientation(&self, a: &Point2D, b: &Point2D, c: &Point2D) -> Ordering {
+        let det = orient2d(a, b, c);
+        let length = a.distance_to(b);
+        if length == 0.0 || det.abs() / length <= self.bound(c.x.abs().max(c.y.abs())) {
+            return Ordering::Equal;
+        }
+        det.canonical_cmp(&0.0)
+    }
+}
+
+#[cfg(test)]
+mod test_tolerance {
+    use super::*;
+
+    #[test]
+    fn test_equal() {
+        let tolerance = Tolerance::default();
+        assert!(tolerance.is_equal(1.0, 1.0 + 1e-9));
+        assert!(!tolerance.is_equal(1.0, 1.0 + 1e-7));
+        assert!(!tolerance.is_equal(1e9, 1e9 + 1e-6));
+        assert!(tolerance.is_zero(-1e-9));
+
+        let relative = Tolerance {
+            relative: 1e-12,
+            ..Default::default()
+        };
+        assert!(relative.is_equal(1e9, 1e9 + 1e-6));
+        assert!(relative.is_equal(1.0f32, 1.0 + 1e-9));
+
+        let exact = Tolerance::exact();
+        assert!(!exact.is_equal(0.1 + 0.2, 0.3));
+        assert!(exact.is_equal(0.5, 0.5));
+    }
+
+    #[test]
+    fn test_snap() {
+        let p = Point2D {
+            x: 1.0000000001,
+            y: 2.0,
+        };
+        assert_eq!(Point2D { x: 1.0, y: 2.0 }, Tolerance::default().snap(p));
+        assert_eq!(p, Tolerance::exact().snap(p));
+    }
+
+    #[test]
+    fn test_orientation() {
+        let a = Point2D { x: 0.0, y: 0.0 };
+        let b = Point2D { x: 2.0, y: 0.0 };
+        let near = Point2D { x: 1.0, y: 1e-9 };
+        assert_eq!(
+            Ordering::Equal,
+            Tolerance::default().orientation(&a, &b, &near)
+        );
+        assert_eq!(
+            Ordering::Greater,
+            Tolerance::exact().orientation(&a, &b, &near)
+        );
+        assert_eq!(
+            Ordering::Less,
+            Tolerance::default().orientation(&a, &b, &Point2D { x: 1.0, y: -1.0 })
+        );
+    }
+}
@@ -2,53 +2,323 @@
 //!
 //! Provides tools like file operations and float operations for the computational geometry library [cg_library](crate).
 
+use crate::error::CgError;
 use crate::linesegment2d::LineSegment2D;
-use crate::point2d::Point2D;
-use crate::util::eventpoint::{EventPoint, EventType};
+use crate::point2d::{Point, Point2D};
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+use crate::util::intersection::Intersection;
 use crate::util::sweepline::SweepLine;
-use std::collections::BTreeSet;
 use std::fs;
 use std::io::Write;
+use std::str::FromStr;
 
 /// Returns the counter clock wise value for three points.
 ///
 /// This function returns zero if the point r is on the line stretched by the points p and q.
 /// It returns a value smaller than zero if the point r follows in a clock wise direction. If it is
 /// bigger than zero if the point r follows in a counter clock wise direction.
-pub fn ccw(p: &Point2D, q: &Point2D, r: &Point2D) -> f64 {
-    return (p.x * q.y - p.y * q.x) + (q.x * r.y - q.y * r.x) + (p.y * r.x - p.x * r.y);
+///
+/// The value is a plain floating point expression, so its sign can be wrong for nearly colinear
+/// points. Use [orient2d] if only the sign is of interest.
+pub fn ccw<T: Scalar>(p: &Point<T>, q: &Point<T>, r: &Point<T>) -> T {
+    (*q - *p).cross(*r - *p)
+}
+
+/// Half of the machine epsilon, which bounds the relative error of a single floating point
+/// operation.
+const EPSILON: f64 = f64::EPSILON / 2.0;
+
+/// The relative error bound of the fast stage of [orient2d].
+const CCW_ERRBOUND_A: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+
+/// The relative error bound of the fast stage of [incircle].
+const ICC_ERRBOUND_A: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Returns the orientation of three points with an exact sign.
+///
+/// The value is positive if the points `a`, `b` and `c` follow in a counter clock wise direction,
+/// negative if they follow in a clock wise direction and zero iff they are colinear. It has the
+/// same sign as [ccw], but the sign is always correct.
+///
+/// The determinant is evaluated in floating point arithmetic first. Only if its error bound
+/// does not guarantee the sign, it is evaluated again exactly with floating point expansions, as
+/// described by Shewchuk in "Adaptive Precision Floating-Point Arithmetic and Fast Robust
+/// Geometric Predicates".
+///
+/// # Examples
+/// ```
+/// use cg_library::point2d::Point2D;
+/// use cg_library::tools2d::orient2d;
+/// let a = Point2D { x: 0.5, y: 0.5 };
+/// let b = Point2D { x: 12.0, y: 12.0 };
+/// let c = Point2D { x: 24.0, y: 24.0 };
+/// assert_eq!(0.0, orient2d(&a, &b, &c));
+/// ```
+pub fn orient2d(a: &Point2D, b: &Point2D, c: &Point2D) -> f64 {
+    let det_left = (a.x - c.x) * (b.y - c.y);
+    let det_right = (a.y - c.y) * (b.x - c.x);
+    let det = det_left - det_right;
+
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let errbound = CCW_ERRBOUND_A * det_sum;
+    if det >= errbound || -det >= errbound {
+        return det;
+    }
+
+    let (acx, acy) = (difference(a.x, c.x), difference(a.y, c.y));
+    let (bcx, bcy) = (difference(b.x, c.x), difference(b.y, c.y));
+    let det = expansion_diff(
+        &expansion_product(&acx, &bcy),
+        &expansion_product(&acy, &bcx),
+    );
+    *det.last().unwrap()
+}
+
+/// Returns the position of a point relative to the circle through three other points with an
+/// exact sign.
+///
+/// The value is positive if the point `d` lies inside the circle through `a`, `b` and `c`,
+/// negative if it lies outside and zero iff all four points lie on one circle. The points `a`,
+/// `b` and `c` need to follow in a counter clock wise direction, otherwise the sign is reversed.
+///
+/// Like [orient2d], the determinant is only evaluated exactly if the floating point result is
+/// not certain.
+///
+/// # Examples
+/// ```
+/// use cg_library::point2d::Point2D;
+/// use cg_library::tools2d::incircle;
+/// let a = Point2D { x: 1.0, y: 0.0 };
+/// let b = Point2D { x: 0.0, y: 1.0 };
+/// let c = Point2D { x: -1.0, y: 0.0 };
+/// assert!(incircle(&a, &b, &c, &Point2D::new()) > 0.0);
+/// assert_eq!(0.0, incircle(&a, &b, &c, &Point2D { x: 0.0, y: -1.0 }));
+/// ```
+pub fn incircle(a: &Point2D, b: &Point2D, c: &Point2D, d: &Point2D) -> f64 {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    let errbound = ICC_ERRBOUND_A * permanent;
+    if det > errbound || -det > errbound {
+        return det;
+    }
+
+    let (adx, ady) = (difference(a.x, d.x), difference(a.y, d.y));
+    let (bdx, bdy) = (difference(b.x, d.x), difference(b.y, d.y));
+    let (cdx, cdy) = (difference(c.x, d.x), difference(c.y, d.y));
+    let lift =
+        |x: &[f64], y: &[f64]| expansion_sum(&expansion_product(x, x), &expansion_product(y, y));
+    let cross = |x1: &[f64], y1: &[f64], x2: &[f64], y2: &[f64]| {
+        expansion_diff(&expansion_product(x1, y1), &expansion_product(x2, y2))
+    };
+
+    let a_term = expansion_product(&lift(&adx, &ady), &cross(&bdx, &cdy, &cdx, &bdy));
+    let b_term = expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &ady, &adx, &cdy));
+    let c_term = expansion_product(&lift(&cdx, &cdy), &cross(&adx, &bdy, &bdx, &ady));
+    let det = expansion_sum(&expansion_sum(&a_term, &b_term), &c_term);
+    *det.last().unwrap()
+}
+
+/// Returns the rounded sum of two values and its roundoff error.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Returns the rounded sum of two values and its roundoff error, given that `|a| >= |b|`.
+fn fast_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+/// Returns the rounded product of two values and its roundoff error.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+/// Returns the exact difference of two values as an expansion.
+///
+/// An expansion is a sum of non overlapping floating point values, ordered by increasing
+/// magnitude. Zero values are left out, except for the expansion of zero itself.
+fn difference(a: f64, b: f64) -> Vec<f64> {
+    let (x, y) = two_sum(a, -b);
+    if y == 0.0 {
+        vec![x]
+    } else {
+        vec![y, x]
+    }
+}
+
+/// Returns the exact sum of an expansion and a value as an expansion.
+fn grow_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h: Vec<f64> = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &e_now in e {
+        let (q_new, h_now) = two_sum(q, e_now);
+        q = q_new;
+        if h_now != 0.0 {
+            h.push(h_now);
+        }
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+/// Returns the exact sum of two expansions as an expansion.
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter()
+        .fold(e.to_vec(), |h, &f_now| grow_expansion(&h, f_now))
+}
+
+/// Returns the exact difference of two expansions as an expansion.
+fn expansion_diff(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter()
+        .fold(e.to_vec(), |h, &f_now| grow_expansion(&h, -f_now))
+}
+
+/// Returns the exact product of an expansion and a value as an expansion.
+fn scale_expansion(e: &[f64], b: f64) -> Vec<f64> {
+    let mut h: Vec<f64> = Vec::with_capacity(2 * e.len());
+    let (mut q, h_now) = two_product(e[0], b);
+    if h_now != 0.0 {
+        h.push(h_now);
+    }
+    for &e_now in &e[1..] {
+        let (product, product_err) = two_product(e_now, b);
+        let (sum, h_now) = two_sum(q, product_err);
+        if h_now != 0.0 {
+            h.push(h_now);
+        }
+        let (q_new, h_now) = fast_two_sum(product, sum);
+        q = q_new;
+        if h_now != 0.0 {
+            h.push(h_now);
+        }
+    }
+    if q != 0.0 || h.is_empty() {
+        h.push(q);
+    }
+    h
+}
+
+/// Returns the exact product of two expansions as an expansion.
+fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(vec![0.0], |h, &f_now| {
+        expansion_sum(&h, &scale_expansion(e, f_now))
+    })
+}
+
+/// This function parses a single value, like a coordinate, of a string.
+///
+/// Surrounding whitespace is ignored, anything else that is no value results in a
+/// [format error](CgError::Format).
+pub fn parse_value<T: FromStr>(value: &str) -> Result<T, CgError> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| CgError::Format(format!("{:?} is no number", value.trim())))
 }
 
 /// This function rounds to a given integer of decimal places to filter numerical errors.
+#[allow(clippy::let_and_return)]
 pub fn round_to_decimal_places(value: f64, decimal_places: u32) -> f64 {
     let multiplier = 10u64.pow(decimal_places);
     let rounded_value = (value * (multiplier as f64)).round() / (multiplier as f64);
     rounded_value
 }
 
+/// This function advances a splitmix64 state and returns its next well distributed value.
+///
+/// It serves as a small reproducible generator without dependencies.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
 /// This function reads a set of line segments from a file.
 ///
-/// It returns this file in the form of a binary tree set of line segments.
+/// It returns this file in the form of a vector of line segments in the order of the file. Every
+/// segment gets its line index in the file as [id](LineSegment2D::id), so that even equal
+/// segments are kept.
+/// Rows with two equal endpoints become [degenerate](LineSegment2D::is_degenerate) segments.
 ///
 /// The file needs to be in the form:
 /// x1 y1 x2 y2
 /// ...
-pub fn read_segments_from_file(path: &str) -> BTreeSet<LineSegment2D> {
-    let content = fs::read_to_string(path).unwrap_or_else(|e| panic!("{e}"));
-    let mut line_segments: BTreeSet<LineSegment2D> = BTreeSet::new();
-    for segment in content.lines() {
-        let values: Vec<&str> = segment.split_whitespace().collect();
-        let p1 = Point2D {
-            x: (values[0].parse::<f64>().unwrap()),
-            y: (values[1].parse::<f64>().unwrap()),
-        };
-        let p2 = Point2D {
-            x: (values[2].parse::<f64>().unwrap()),
-            y: (values[3].parse::<f64>().unwrap()),
+///
+/// Every row is parsed like a [LineSegment2D] from a string, so it may use its displayed form as
+/// well.
+///
+/// # Panics
+/// This function panics if the file can not be read or parsed, see
+/// [try_read_segments_from_file].
+pub fn read_segments_from_file(path: &str) -> Vec<LineSegment2D> {
+    try_read_segments_from_file(path).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// This function reads a set of line segments from a file, like [read_segments_from_file].
+///
+/// It returns an [I/O error](CgError::Io) if the file can not be read and a
+/// [parse error](CgError::Parse) with the line number if a row does not consist of four numbers.
+///
+/// # Examples
+/// ```
+/// use cg_library::tools2d::try_read_segments_from_file;
+/// assert!(try_read_segments_from_file("../data/s_1000_1.dat").is_ok());
+/// assert!(try_read_segments_from_file("../data/missing.dat").is_err());
+/// ```
+pub fn try_read_segments_from_file(path: &str) -> Result<Vec<LineSegment2D>, CgError> {
+    let content = fs::read_to_string(path)?;
+    let mut line_segments: Vec<LineSegment2D> = Vec::new();
+    for (id, segment) in content.lines().enumerate() {
+        let error = |message: String| CgError::Parse {
+            line: id + 1,
+            message,
         };
-        line_segments.insert(LineSegment2D::new(p1, p2));
+        let segment = segment
+            .parse::<LineSegment2D>()
+            .map_err(|e| error(e.to_string()))?;
+        // Equal endpoints are kept as a degenerate segment, unlike in `LineSegment2D::try_new`
+        if segment.p1.is_nan() || segment.p2.is_nan() {
+            return Err(error(CgError::NanCoordinate.to_string()));
+        }
+        line_segments.push(segment.with_id(id));
     }
-    return line_segments;
+    Ok(line_segments)
 }
 
 /// This function writes a vector of points into a file.
@@ -57,47 +327,178 @@ pub fn read_segments_from_file(path: &str) -> BTreeSet<LineSegment2D> {
 /// x1 y1
 /// x2 y2
 /// ...
+///
+/// # Panics
+/// This function panics if the file can not be written, see [try_save_points].
 pub fn save_points(points: Vec<Point2D>, path: &str) {
-    let mut file = fs::File::create(path).expect("Failed to create file!");
+    try_save_points(points, path).unwrap_or_else(|e| panic!("{e}"));
+}
+
+/// This function writes a vector of points into a file, like [save_points].
+///
+/// It returns an [I/O error](CgError::Io) if the file can not be written.
+pub fn try_save_points(points: Vec<Point2D>, path: &str) -> Result<(), CgError> {
+    let mut file = fs::File::create(path)?;
     for point in points {
-        write!(file, "{} {}\n", point.x, point.y).expect("Failed to write to file!");
+        writeln!(file, "{} {}", point.x, point.y)?;
     }
+    Ok(())
 }
 
-/// This function calculates the intersection points of a set of line segments using the
+/// This function calculates the intersections of a set of line segments using the
 /// bently ottmann algorithm.
 ///
+/// Every [intersection](crate::util::intersection::Intersection) contains the point as well as the
+/// ids of the participating segments. The [id](LineSegment2D::id) of every segment is set to its
+/// position in the given vector, which is the line index for segments of
+/// [read_segments_from_file].
+///
 /// # Examples
 /// ```
 /// use cg_library::tools2d::*;
 /// let segments = read_segments_from_file("../data/s_1000_10.dat");
 /// let intersections = bently_ottmann(segments);
+/// let points: Vec<_> = intersections.iter().map(|i| i.point).collect();
 /// ```
-pub fn bently_ottmann(segments: BTreeSet<LineSegment2D>) -> Vec<Point2D> {
-    let mut sl: SweepLine = SweepLine::new();
-    for segment in segments {
-        if segment.line.is_vertical() {
-            continue;
-        }
+pub fn bently_ottmann(segments: Vec<LineSegment2D>) -> Vec<Intersection> {
+    bently_ottmann_with_tolerance(segments, Tolerance::default())
+}
+
+/// This function calculates the intersections of a set of line segments using the
+/// bently ottmann algorithm with the given [tolerance](Tolerance).
+///
+/// The intersections are the same as the ones of [bently_ottmann], which uses the
+/// [default](Tolerance::default) tolerance for coordinates of unit scale. Datasets with large
+/// coordinates need a relative epsilon and fewer decimal places.
+///
+/// # Examples
+/// ```
+/// use cg_library::tolerance::Tolerance;
+/// use cg_library::tools2d::*;
+/// let segments = read_segments_from_file("../data/s_1000_10.dat");
+/// let tolerance = Tolerance {
+///     digits: Some(6),
+///     ..Default::default()
+/// };
+/// let intersections = bently_ottmann_with_tolerance(segments, tolerance);
+/// assert_eq!(796, intersections.len());
+/// ```
+pub fn bently_ottmann_with_tolerance(
+    segments: Vec<LineSegment2D>,
+    tolerance: Tolerance,
+) -> Vec<Intersection> {
+    let mut sl: SweepLine = SweepLine::with_tolerance(tolerance);
+    for (id, segment) in segments.into_iter().enumerate() {
+        sl.insert_segment(segment.with_id(id));
+    }
 
-        sl.event_queue.insert(EventPoint {
-            point: segment.p1,
-            event_type: EventType::IsLeftEndpoint,
-            first_line: segment,
-            second_line: None,
-        });
-        sl.event_queue.insert(EventPoint {
-            point: segment.p2,
-            event_type: EventType::IsRightEndpoint,
-            first_line: segment,
-            second_line: None,
-        });
+    while !sl.event_queue.is_empty() {
+        sl.process_next_event();
+    }
+    sl.intersections.sort();
+
+    sl.intersections
+}
+
+/// This function calculates the intersections of a set of line segments using the
+/// bently ottmann algorithm with the [exact](crate::exact) kernel.
+///
+/// The intersections are the same as the ones of [bently_ottmann], but the points are calculated
+/// exactly and converted to the nearest [Point2D] only for the output. It returns an error if a
+/// coordinate is not finite, since it has no exact rational.
+///
+/// # Examples
+/// ```
+/// use cg_library::tools2d::*;
+/// let segments = read_segments_from_file("../data/s_1000_10.dat");
+/// let intersections = bently_ottmann_exact(segments).unwrap();
+/// assert_eq!(796, intersections.len());
+/// ```
+#[cfg(feature = "exact")]
+pub fn bently_ottmann_exact(segments: Vec<LineSegment2D>) -> Result<Vec<Intersection>, CgError> {
+    let mut sl: SweepLine = SweepLine::new_exact();
+    for (id, segment) in segments.into_iter().enumerate() {
+        sl.try_insert_segment(segment.with_id(id))?;
     }
 
     while !sl.event_queue.is_empty() {
         sl.process_next_event();
     }
-    sl.intersection_points.sort();
+    sl.intersections.sort();
+
+    Ok(sl.intersections)
+}
+
+#[cfg(test)]
+mod test_tools2d {
+    use super::*;
+
+    #[test]
+    fn test_read_error() {
+        assert!(matches!(
+            try_read_segments_from_file("../data/missing.dat"),
+            Err(CgError::Io(_))
+        ));
+
+        let path = std::env::temp_dir().join("cg_library_test_read_error.dat");
+        fs::write(&path, "0 0 1 1\n0 1 1\n").unwrap();
+        let result = try_read_segments_from_file(path.to_str().unwrap());
+        assert!(matches!(result, Err(CgError::Parse { line: 2, .. })));
+
+        fs::write(&path, "0 0 1 1\n0 x 1 0\n").unwrap();
+        let result = try_read_segments_from_file(path.to_str().unwrap());
+        assert!(matches!(result, Err(CgError::Parse { line: 2, .. })));
+
+        fs::write(&path, "0 0 1 1\n0 1 1 0\n").unwrap();
+        assert_eq!(
+            2,
+            try_read_segments_from_file(path.to_str().unwrap())
+                .unwrap()
+                .len()
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_orient2d() {
+        let b = Point2D { x: 12.0, y: 12.0 };
+        let c = Point2D { x: 24.0, y: 24.0 };
+        assert!(orient2d(&Point2D { x: 0.0, y: 1.0 }, &b, &c) > 0.0);
+        assert!(orient2d(&Point2D { x: 1.0, y: 0.0 }, &b, &c) < 0.0);
 
-    return sl.intersection_points;
+        // Points in the grid of the smallest steps around (0.5,0.5), the sign depends only on the
+        // side of the diagonal
+        let step = f64::EPSILON / 2.0;
+        for i in 0..32 {
+            for j in 0..32 {
+                let a = Point2D {
+                    x: 0.5 + i as f64 * step,
+                    y: 0.5 + j as f64 * step,
+                };
+                assert_eq!(Some(j.cmp(&i)), orient2d(&a, &b, &c).partial_cmp(&0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_incircle() {
+        let a = Point2D { x: 1.0, y: 0.0 };
+        let b = Point2D { x: 0.0, y: 1.0 };
+        let c = Point2D { x: -1.0, y: 0.0 };
+        assert_eq!(0.0, incircle(&a, &b, &c, &Point2D { x: 0.0, y: -1.0 }));
+        assert!(incircle(&a, &b, &c, &Point2D { x: 0.0, y: 2.0 }) < 0.0);
+        assert!(incircle(&c, &b, &a, &Point2D { x: 0.0, y: 0.5 }) < 0.0);
+
+        // Just inside and just outside of the circle at the bottom
+        let inside = Point2D {
+            x: 0.0,
+            y: -1.0 + f64::EPSILON,
+        };
+        let outside = Point2D {
+            x: 0.0,
+            y: -1.0 - f64::EPSILON,
+        };
+        assert!(incircle(&a, &b, &c, &inside) > 0.0);
+        assert!(incircle(&a, &b, &c, &outside) < 0.0);
+    }
 }
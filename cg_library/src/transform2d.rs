@@ -0,0 +1,315 @@
+//! Affine transformation in a 2-Dimensional vector space.
+//!
+//! Provides an affine transformation struct for the computational geometry library
+//! [cg_library](crate), which applies to every geometry type.
+
+use crate::line2d::Line;
+use crate::linesegment2d::LineSegment;
+use crate::point2d::Point;
+use crate::polygon2d::Polygon;
+use crate::scalar::Scalar;
+use crate::vector2d::Vector;
+use num_traits::Float;
+use std::ops::Mul;
+
+/// An affine transformation in a 2D vector space.
+///
+/// The transformation is a 3x3 matrix that maps the homogeneous coordinates `(x,y,1)` of a point,
+/// its last row is always `(0,0,1)`. Transformations are built from the
+/// [identity](Transform::identity) by chaining translations, scalings, rotations and shearings,
+/// each applied after the previous ones. Its coordinates are of any
+/// [scalar](crate::scalar::Scalar) type, [Transform2D] is the transformation with `f64`
+/// coordinates.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::point2d::Point;
+/// use cg_library::transform2d::Transform;
+///
+/// let t: Transform<i64> = Transform::identity().scale(2, 3).translate(1, 1);
+/// assert_eq!(Point { x: 3, y: 4 }, t.apply(&Point { x: 1, y: 1 }));
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Transform<T> {
+    /// The rows of the matrix.
+    pub matrix: [[T; 3]; 3],
+}
+
+/// An affine transformation in a 2D vector space with `f64` coordinates.
+///
+/// # Example
+///
+/// This converts pixels of a map into kilometres, where the map is 640km wide and 876km high:
+/// ```
+/// use cg_library::point2d::Point2D;
+/// use cg_library::transform2d::Transform2D;
+///
+/// let to_km = Transform2D::identity().scale(640.0 / 320.0, 876.0 / 438.0);
+/// assert_eq!(Point2D { x: 20.0, y: 4.0 }, to_km.apply(&Point2D { x: 10.0, y: 2.0 }));
+/// ```
+pub type Transform2D = Transform<f64>;
+
+/// A geometry that can be mapped by an affine [transformation](Transform).
+pub trait Transformable<T> {
+    /// Returns the geometry mapped by the transformation.
+    fn transform(&self, t: &Transform<T>) -> Self;
+}
+
+/// This trait allows the product of two transformations, which applies the right one first.
+impl<T: Scalar> Mul for Transform<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let mut matrix = [[T::zero(); 3]; 3];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..3).fold(T::zero(), |sum, k| {
+                    sum + self.matrix[i][k] * other.matrix[k][j]
+                });
+            }
+        }
+        Transform { matrix }
+    }
+}
+
+/// This trait returns the [identity](Transform::identity).
+impl<T: Scalar> Default for Transform<T> {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+impl<T: Scalar> Transform<T> {
+    /// Returns the transformation that maps every point onto itself.
+    pub fn identity() -> Transform<T> {
+        let (o, l) = (T::zero(), T::one());
+        Transform {
+            matrix: [[l, o, o], [o, l, o], [o, o, l]],
+        }
+    }
+
+    /// Returns a transformation from the linear part `(a b; c d)` and the translation `(e,f)`.
+    fn from_parts(a: T, b: T, c: T, d: T, e: T, f: T) -> Transform<T> {
+        let (o, l) = (T::zero(), T::one());
+        Transform {
+            matrix: [[a, b, e], [c, d, f], [o, o, l]],
+        }
+    }
+
+    /// Returns the transformation that applies this one first and then the other one.
+    pub fn compose(self, other: &Transform<T>) -> Transform<T> {
+        *other * self
+    }
+
+    /// Returns this transformation followed by a translation.
+    pub fn translate(self, dx: T, dy: T) -> Transform<T> {
+        let (o, l) = (T::zero(), T::one());
+        self.compose(&Transform::from_parts(l, o, o, l, dx, dy))
+    }
+
+    /// Returns this transformation followed by a scaling at the origin.
+    ///
+    /// A negative factor mirrors the axis.
+    pub fn scale(self, sx: T, sy: T) -> Transform<T> {
+        let o = T::zero();
+        self.compose(&Transform::from_parts(sx, o, o, sy, o, o))
+    }
+
+    /// Returns this transformation followed by a shearing, which moves `x` by `shx * y` and `y`
+    /// by `shy * x`.
+    pub fn shear(self, shx: T, shy: T) -> Transform<T> {
+        let (o, l) = (T::zero(), T::one());
+        self.compose(&Transform::from_parts(l, shx, shy, l, o, o))
+    }
+
+    /// Returns the determinant of the linear part.
+    ///
+    /// It is the factor areas are scaled by, a negative determinant mirrors the orientation.
+    pub fn determinant(&self) -> T {
+        let m = &self.matrix;
+        m[0][0] * m[1][1] - m[0][1] * m[1][0]
+    }
+
+    /// Returns the inverse transformation, or `None` if the transformation is not invertible.
+    pub fn invert(&self) -> Option<Transform<T>> {
+        let det = self.determinant();
+        if det == T::zero() {
+            return None;
+        }
+
+        let m = &self.matrix;
+        let (a, b, c, d) = (m[1][1] / det, -m[0][1] / det, -m[1][0] / det, m[0][0] / det);
+        let (e, f) = (m[0][2], m[1][2]);
+        Some(Transform::from_parts(
+            a,
+            b,
+            c,
+            d,
+            -(a * e + b * f),
+            -(c * e + d * f),
+        ))
+    }
+
+    /// Returns the geometry mapped by the transformation, see [Transformable].
+    pub fn apply<G: Transformable<T>>(&self, geometry: &G) -> G {
+        geometry.transform(self)
+    }
+}
+
+impl<T: Scalar + Float> Transform<T> {
+    /// Returns this transformation followed by a counter clock wise rotation around the origin
+    /// by an angle in radians.
+    pub fn rotate(self, angle: T) -> Transform<T> {
+        let (sin, cos) = angle.sin_cos();
+        self.compose(&Transform::from_parts(
+            cos,
+            -sin,
+            sin,
+            cos,
+            T::zero(),
+            T::zero(),
+        ))
+    }
+}
+
+impl<T: Scalar> Transformable<T> for Point<T> {
+    fn transform(&self, t: &Transform<T>) -> Point<T> {
+        let m = &t.matrix;
+        Point {
+            x: m[0][0] * self.x + m[0][1] * self.y + m[0][2],
+            y: m[1][0] * self.x + m[1][1] * self.y + m[1][2],
+        }
+    }
+}
+
+/// A vector is only mapped by the linear part, since it has no position.
+impl<T: Scalar> Transformable<T> for Vector<T> {
+    fn transform(&self, t: &Transform<T>) -> Vector<T> {
+        let m = &t.matrix;
+        Vector {
+            x: m[0][0] * self.x + m[0][1] * self.y,
+            y: m[1][0] * self.x + m[1][1] * self.y,
+        }
+    }
+}
+
+/// The endpoints are mapped and sorted again, the id of the segment is kept. If the
+/// transformation maps both endpoints onto the same point, the segment becomes
+/// [degenerate](LineSegment::is_degenerate).
+impl<T: Scalar> Transformable<T> for LineSegment<T> {
+    fn transform(&self, t: &Transform<T>) -> LineSegment<T> {
+        LineSegment::new(t.apply(&self.p1), t.apply(&self.p2)).with_id(self.id)
+    }
+}
+
+/// The coefficients are multiplied with the adjugate of the matrix, which is the inverse scaled
+/// by the determinant, so the direction of the line is mapped like a [vector](Vector). The
+/// transformation needs to be invertible, otherwise the image of the line is no line.
+impl<T: Scalar> Transformable<T> for Line<T> {
+    fn transform(&self, t: &Transform<T>) -> Line<T> {
+        let m = &t.matrix;
+        let (a, b, c) = (self.a, self.b, self.c);
+        Line {
+            a: a * m[1][1] - b * m[1][0],
+            b: b * m[0][0] - a * m[0][1],
+            c: a * (m[0][1] * m[1][2] - m[1][1] * m[0][2])
+                + b * (m[1][0] * m[0][2] - m[0][0] * m[1][2])
+                + c * t.determinant(),
+        }
+    }
+}
+
+/// The points are mapped in their order, so the polygon keeps its orientation unless the
+/// [determinant](Transform::determinant) is negative.
+impl<T: Scalar> Transformable<T> for Polygon<T> {
+    fn transform(&self, t: &Transform<T>) -> Polygon<T> {
+        Polygon::new(self.points.iter().map(|p| t.apply(p)).collect())
+    }
+}
+
+#[cfg(test)]
+mod test_transform2d {
+    use super::*;
+    use crate::line2d::Line2D;
+    use crate::point2d::Point2D;
+    use crate::polygon2d::Polygon2D;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_compose() {
+        let p: Point<i64> = Point { x: 1, y: 2 };
+        let t1: Transform<i64> = Transform::identity().translate(1, 0).scale(2, 2);
+        let t2: Transform<i64> = Transform::identity().scale(2, 2).translate(1, 0);
+        assert_eq!(Point { x: 4, y: 4 }, t1.apply(&p));
+        assert_eq!(Point { x: 3, y: 4 }, t2.apply(&p));
+        assert_eq!(
+            t1,
+            Transform::identity()
+                .translate(1, 0)
+                .compose(&t2.translate(-1, 0))
+        );
+        assert_eq!(
+            Point { x: 5, y: 3 },
+            Transform::identity().shear(2, 1).apply(&p)
+        );
+        assert_eq!(Vector { x: 2, y: 4 }, t1.apply(&Vector { x: 1, y: 2 }));
+        assert_eq!(Transform::identity(), Transform::<i64>::default());
+    }
+
+    #[test]
+    fn test_invert() {
+        let t: Transform2D = Transform2D::identity()
+            .scale(2.0, 4.0)
+            .shear(1.0, 0.0)
+            .translate(3.0, -1.0);
+        let p = Point2D { x: 1.5, y: -2.0 };
+        assert_eq!(p, t.invert().unwrap().apply(&t.apply(&p)));
+        assert_eq!(Transform2D::identity(), t.compose(&t.invert().unwrap()));
+        assert_eq!(8.0, t.determinant());
+
+        let singular: Transform2D = Transform2D::identity().scale(1.0, 0.0);
+        assert_eq!(None, singular.invert());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let t: Transform2D = Transform2D::identity().rotate(FRAC_PI_2);
+        let p = t.apply(&Point2D { x: 2.0, y: 0.0 });
+        assert!(p.x.abs() < 1e-12 && (p.y - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_geometry() {
+        let t: Transform2D = Transform2D::identity().scale(-2.0, 3.0).translate(1.0, 1.0);
+
+        let s = LineSegment::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 1.0, y: 1.0 }).with_id(7);
+        let mapped = t.apply(&s);
+        assert_eq!(Point2D { x: -1.0, y: 4.0 }, mapped.p1);
+        assert_eq!(Point2D { x: 1.0, y: 1.0 }, mapped.p2);
+        assert_eq!(7, mapped.id);
+
+        // The mapped line runs through the mapped points and keeps its direction
+        let l =
+            Line2D::from_point_and_point(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 1.0, y: 1.0 });
+        let mapped = t.apply(&l);
+        assert!(mapped.contains(&Point2D { x: 1.0, y: 1.0 }));
+        assert!(mapped.contains(&Point2D { x: -1.0, y: 4.0 }));
+        assert_eq!(t.apply(&l.direction()), mapped.direction());
+
+        // Areas are scaled by the determinant
+        let square = Polygon2D::new(vec![
+            Point2D { x: 0.0, y: 0.0 },
+            Point2D { x: 1.0, y: 0.0 },
+            Point2D { x: 1.0, y: 1.0 },
+            Point2D { x: 0.0, y: 1.0 },
+            Point2D { x: 0.0, y: 0.0 },
+        ]);
+        let mapped = t.apply(&square);
+        assert_eq!(
+            square.calculate_area() * t.determinant(),
+            mapped.calculate_area()
+        );
+    }
+}
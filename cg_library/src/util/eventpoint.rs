@@ -3,13 +3,21 @@
 //! Especially usefull for the event queue in the [bently
 //! ottmann](crate::tools2d::bently_ottmann) algorithm where it acts as the x-structure.
 
+#[cfg(feature = "exact")]
+use crate::exact::ExactPoint2D;
 use crate::linesegment2d::LineSegment2D;
 use crate::point2d::Point2D;
 use std::cmp::Ordering;
 use std::fmt;
 
 /// An event in the bently ottmann algorithm can have one of three types.
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Events at the same point are handled in the order: right endpoints, intersections and left
+/// endpoints. That way ending segments leave the y-structure before new ones are inserted. The
+/// [sweep line](crate::util::sweepline::SweepLine) keeps the ended segments until it leaves the
+/// x-coordinate, so that their contacts at the point are still reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EventType {
     /// In case of a point being the left endpoint of a segment.
     IsLeftEndpoint,
@@ -32,8 +40,28 @@ impl fmt::Display for EventType {
     }
 }
 
+/// This trait is added to allow event types at the same point to be ordered.
+impl Ord for EventType {
+    fn cmp(&self, other: &EventType) -> Ordering {
+        let rank = |e: &EventType| match e {
+            EventType::IsRightEndpoint => 0,
+            EventType::IsIntersection => 1,
+            EventType::IsLeftEndpoint => 2,
+        };
+        rank(self).cmp(&rank(other))
+    }
+}
+
+/// This trait is added to allow event types at the same point to be ordered.
+impl PartialOrd for EventType {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /// An event is handled by an event queue. They need to be sorted and they have certain contents.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventPoint {
     /// The point associated with the event.
     pub point: Point2D,
@@ -44,11 +72,37 @@ pub struct EventPoint {
     /// This is the segment of the point associated with an event.
     pub first_line: LineSegment2D,
 
-    /// In case of an intersection event is this the second line associated with the event.
+    /// In case of an intersection event these are all other lines running through the point.
+    ///
+    /// One intersection can have more than two lines being part of, they form the bundle of the
+    /// event together with the `first_line`.
+    pub other_lines: Vec<LineSegment2D>,
+
+    /// The exact point of the event in a sweep line with the [exact](crate::exact) kernel, the
+    /// `point` is its nearest [Point2D].
     ///
-    /// In the future this could be replaced by a vector, if one intersection allows more than two
-    /// lines being part of.
-    pub second_line: Option<LineSegment2D>,
+    /// If both events have an exact point, they are ordered after it instead of the `point`.
+    #[cfg(feature = "exact")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub exact: Option<ExactPoint2D>,
+}
+
+impl EventPoint {
+    /// Returns all lines associated with the event, starting with the `first_line`.
+    pub fn lines(&self) -> Vec<LineSegment2D> {
+        let mut lines = vec![self.first_line];
+        lines.extend(self.other_lines.iter().copied());
+        lines
+    }
+
+    /// Compares the points of two events, which are the exact points if both events have one.
+    fn cmp_point(&self, other: &EventPoint) -> Ordering {
+        #[cfg(feature = "exact")]
+        if let (Some(p), Some(q)) = (&self.exact, &other.exact) {
+            return p.cmp(q);
+        }
+        self.point.cmp(&other.point)
+    }
 }
 
 /// This trait needs to be implemented to satisfy PartialOrd, it is not yet used.
@@ -57,27 +111,37 @@ impl Eq for EventPoint {}
 /// This trait is added to allow events to be ordered.
 ///
 /// Events are ordered after rising x-coordinates at first and at tie after the y-coordinates of
-/// the associated point.
+/// the associated point. Events at the same point are ordered after their [type](EventType) and
+/// endpoint events at last after their segment and its [id](LineSegment2D::id), so that they do
+/// not collapse in an event queue. All intersection events at the same point are equal, they
+/// are meant to be merged into one bundle.
 impl Ord for EventPoint {
     fn cmp(&self, other: &EventPoint) -> Ordering {
-        self.point.partial_cmp(&other.point).unwrap()
+        self.cmp_point(other)
+            .then_with(|| self.event_type.cmp(&other.event_type))
+            .then_with(|| {
+                if self.event_type == EventType::IsIntersection {
+                    Ordering::Equal
+                } else {
+                    self.first_line
+                        .cmp(&other.first_line)
+                        .then_with(|| self.first_line.id.cmp(&other.first_line.id))
+                }
+            })
     }
 }
 
 /// This trait is added to allow events to be ordered.
-///
-/// Events are ordered after rising x-coordinates at first and at tie after the y-coordinates of
-/// the associated point.
 impl PartialOrd for EventPoint {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.point.cmp(&other.point))
+        Some(self.cmp(other))
     }
 }
 
-/// This trait allows the comparison of two points, it returns true if two points match.
+/// This trait allows the comparison of two events, it returns true if they are ordered equally.
 impl PartialEq for EventPoint {
     fn eq(&self, other: &Self) -> bool {
-        self.point == other.point
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -101,7 +165,7 @@ impl fmt::Debug for EventPoint {
             .field("Point: ", &self.point)
             .field("Line: ", &self.first_line)
             .field("Event: ", &self.event_type)
-            .field("LineB: ", &self.second_line)
+            .field("Others: ", &self.other_lines)
             .finish()
     }
 }
@@ -112,6 +176,7 @@ mod test_eventpoints {
     use std::collections::BTreeSet;
 
     #[test]
+    #[allow(clippy::bool_assert_comparison)]
     fn test_order() {
         let p1: Point2D = Point2D { x: 0.0, y: 0.0 };
         let p2: Point2D = Point2D { x: 1.0, y: 1.0 };
@@ -120,17 +185,41 @@ mod test_eventpoints {
             point: s1.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e2: EventPoint = EventPoint {
             point: s1.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         assert_eq!(true, e2 > e1);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let s2 =
+            LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 2.0, y: 0.0 }).with_id(1);
+        let e = EventPoint {
+            point: Point2D { x: 1.0, y: 1.0 },
+            event_type: EventType::IsIntersection,
+            first_line: s1,
+            other_lines: vec![s2],
+            #[cfg(feature = "exact")]
+            exact: None,
+        };
+        let parsed: EventPoint = serde_json::from_str(&serde_json::to_string(&e).unwrap()).unwrap();
+        assert_eq!(e.point, parsed.point);
+        assert_eq!(e.event_type, parsed.event_type);
+        assert_eq!(e.lines(), parsed.lines());
+    }
+
     #[test]
     fn test_btree() {
         let mut queue: BTreeSet<EventPoint> = BTreeSet::new();
@@ -142,13 +231,17 @@ mod test_eventpoints {
             point: s1.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e1_2: EventPoint = EventPoint {
             point: s1.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         let p1: Point2D = Point2D { x: 0.0, y: 0.0 };
@@ -158,13 +251,17 @@ mod test_eventpoints {
             point: s2.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s2,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e2_2: EventPoint = EventPoint {
             point: s2.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s2,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         let p1: Point2D = Point2D { x: 1.0, y: 1.0 };
@@ -172,7 +269,9 @@ mod test_eventpoints {
             point: p1,
             event_type: EventType::IsIntersection,
             first_line: s1,
-            second_line: Some(s2),
+            other_lines: vec![s2],
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         queue.extend(vec![e1_1, e1_2, e2_1, e2_2, e12]);
@@ -183,4 +282,66 @@ mod test_eventpoints {
         assert_eq!(EventType::IsRightEndpoint, i.next().unwrap().event_type);
         assert_eq!(EventType::IsRightEndpoint, i.next().unwrap().event_type);
     }
+
+    #[test]
+    fn test_same_point() {
+        let mut queue: BTreeSet<EventPoint> = BTreeSet::new();
+
+        let p1: Point2D = Point2D { x: 0.0, y: 0.0 };
+        let p2: Point2D = Point2D { x: 1.0, y: 1.0 };
+        let p3: Point2D = Point2D { x: 2.0, y: 0.0 };
+        let p4: Point2D = Point2D { x: 2.0, y: 2.0 };
+        let s1: LineSegment2D = LineSegment2D::new(p1, p2);
+        let s2: LineSegment2D = LineSegment2D::new(p2, p3);
+        let s3: LineSegment2D = LineSegment2D::new(p2, p4);
+        let e1: EventPoint = EventPoint {
+            point: p2,
+            event_type: EventType::IsRightEndpoint,
+            first_line: s1,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
+        };
+        let e2: EventPoint = EventPoint {
+            point: p2,
+            event_type: EventType::IsLeftEndpoint,
+            first_line: s2,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
+        };
+        let e3: EventPoint = EventPoint {
+            point: p2,
+            event_type: EventType::IsLeftEndpoint,
+            first_line: s3,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
+        };
+        let e4: EventPoint = EventPoint {
+            point: p2,
+            event_type: EventType::IsIntersection,
+            first_line: s1,
+            other_lines: vec![s2],
+            #[cfg(feature = "exact")]
+            exact: None,
+        };
+        let e5: EventPoint = EventPoint {
+            point: p2,
+            event_type: EventType::IsIntersection,
+            first_line: s2,
+            other_lines: vec![s3],
+            #[cfg(feature = "exact")]
+            exact: None,
+        };
+
+        // Endpoint events of different segments do not collapse, intersections are merged
+        queue.extend(vec![e3, e2, e1, e4, e5]);
+        assert_eq!(4, queue.len());
+        let mut i = queue.into_iter();
+        assert_eq!(EventType::IsRightEndpoint, i.next().unwrap().event_type);
+        assert_eq!(vec![s1, s2], i.next().unwrap().lines());
+        assert_eq!(s2, i.next().unwrap().first_line);
+        assert_eq!(s3, i.next().unwrap().first_line);
+    }
 }
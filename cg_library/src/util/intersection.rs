@@ -0,0 +1,127 @@
+//! This is the result of an intersection found by the sweep line.
+//!
+//! Especially usefull as output of the [bently ottmann](crate::tools2d::bently_ottmann)
+//! algorithm, where not only the point but also the participating segments are of interest.
+
+use crate::linesegment2d::LineSegment2D;
+use crate::point2d::Point2D;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// An intersection found by the sweep line can be of one of two kinds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IntersectionKind {
+    /// In case of segments crossing or touching in a single point.
+    Point,
+
+    /// In case of colinear segments overlapping in the contained sub-segment.
+    Overlap(LineSegment2D),
+}
+
+/// An intersection point together with all segments that run through it.
+///
+/// The segments are referenced by their index in the input of the
+/// [bently ottmann](crate::tools2d::bently_ottmann) algorithm, so that further algorithms like
+/// splitting segments or building a planar graph can be built on top of the sweep.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::point2d::Point2D;
+/// use cg_library::util::intersection::{Intersection, IntersectionKind};
+/// let i: Intersection = Intersection {
+///     point: Point2D { x: 1.0, y: 1.0 },
+///     segments: vec![0, 3],
+///     kind: IntersectionKind::Point,
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Intersection {
+    /// The point where the segments intersect, or where their overlap starts.
+    pub point: Point2D,
+
+    /// The indices of all segments that run through the point, sorted ascending.
+    pub segments: Vec<usize>,
+
+    /// The kind of the intersection.
+    pub kind: IntersectionKind,
+}
+
+/// This trait needs to be implemented to satisfy PartialOrd, it is not yet used.
+impl Eq for Intersection {}
+
+/// This trait is added to allow intersections to be ordered.
+///
+/// Intersections are ordered after their point at first, at tie point intersections come before
+/// overlaps, which are ordered after their end point, and at last after the segment indices.
+impl Ord for Intersection {
+    fn cmp(&self, other: &Intersection) -> Ordering {
+        let end = |i: &Intersection| match i.kind {
+            IntersectionKind::Point => None,
+            IntersectionKind::Overlap(segment) => Some(segment.p2),
+        };
+        self.point
+            .cmp(&other.point)
+            .then_with(|| end(self).cmp(&end(other)))
+            .then_with(|| self.segments.cmp(&other.segments))
+    }
+}
+
+/// This trait is added to allow intersections to be ordered.
+impl PartialOrd for Intersection {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// This trait allows an intersection to be displayed in the form of `(x,y): [i, j]`.
+///
+/// If the intersection is an overlap, it is displayed as `p1: (x1,y1), p2: (x2,y2): [i, j]`.
+impl fmt::Display for Intersection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            IntersectionKind::Point => write!(f, "{}: {:?}", self.point, self.segments),
+            IntersectionKind::Overlap(segment) => write!(f, "{}: {:?}", segment, self.segments),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_intersection {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let i: Intersection = Intersection {
+            point: Point2D { x: 1.0, y: 2.0 },
+            segments: vec![0, 4],
+            kind: IntersectionKind::Point,
+        };
+        assert_eq!("(1,2): [0, 4]", i.to_string());
+
+        let overlap = LineSegment2D::new(Point2D { x: 1.0, y: 2.0 }, Point2D { x: 2.0, y: 3.0 });
+        let i: Intersection = Intersection {
+            point: overlap.p1,
+            segments: vec![1, 2],
+            kind: IntersectionKind::Overlap(overlap),
+        };
+        assert_eq!("p1: (1,2), p2: (2,3): [1, 2]", i.to_string());
+    }
+
+    #[test]
+    fn test_order() {
+        let i1: Intersection = Intersection {
+            point: Point2D { x: 1.0, y: 2.0 },
+            segments: vec![0, 4],
+            kind: IntersectionKind::Point,
+        };
+        let i2: Intersection = Intersection {
+            point: Point2D { x: 1.0, y: 3.0 },
+            segments: vec![0, 1],
+            kind: IntersectionKind::Point,
+        };
+        assert!(i2 > i1);
+    }
+}
@@ -2,17 +2,26 @@
 //!
 //! This the line that sweeps from left to right above all the event points.
 
-use crate::linesegment2d::LineSegment2D;
+use crate::error::CgError;
+#[cfg(feature = "exact")]
+use crate::exact::{self, ExactIntersection, ExactPoint2D};
+use crate::linesegment2d::{LineSegment2D, SegmentIntersection};
 use crate::point2d::Point2D;
+use crate::tolerance::Tolerance;
+use crate::tools2d::orient2d;
 use crate::util::eventpoint::{EventPoint, EventType};
+use crate::util::intersection::{Intersection, IntersectionKind};
+use crate::util::ystructure::YStructure;
+#[cfg(feature = "exact")]
+use num_traits::Zero;
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use ordered_float::OrderedFloat;
-use std::ops::Bound::{Excluded, Unbounded};
-
 /// This is the heart of the bently ottmann algorithm, it contains all the elements important like
 /// event queue, sweep line and intersection points.
+///
+/// Every event costs $O(\log n)$ in the event queue as well as in the y-structure, so the
+/// algorithm runs in $O((n+k) \log n)$ for $n$ segments and $k$ intersections.
 pub struct SweepLine {
     /// The event queue of the algorithm, it contains all the event points sorted in a binary
     /// tree.
@@ -21,9 +30,8 @@ pub struct SweepLine {
     /// Points are ordered with the event's point coordinate.
     pub event_queue: BTreeSet<EventPoint>,
 
-    /// The y-structure representation of the segments with the `key` being the y-coordinate of the
-    /// associated line that gets updated at every event.
-    segments: BTreeMap<OrderedFloat<f64>, LineSegment2D>,
+    /// The y-structure of the segments, ordered at the point of the current event.
+    segments: YStructure,
 
     /// The current event that is handled at the moment.
     current_event: Option<EventPoint>,
@@ -31,197 +39,694 @@ pub struct SweepLine {
     /// That is the current x-coordinate that the sweepline is at.
     current_x: f64,
 
-    /// This is the current y-coordinate of the event's current y-coordinate.
-    events_order: OrderedFloat<f64>,
+    /// The vertical segments that started at the current x-coordinate of the sweep line.
+    ///
+    /// Vertical segments are not part of the y-structure, since they have no single
+    /// y-coordinate. They are only active as long as the sweep line stays at their x-coordinate.
+    verticals: Vec<LineSegment2D>,
+
+    /// The segments that ended at the current x-coordinate of the sweep line.
+    ///
+    /// Right endpoints are handled before all other events at the same point, so the segments
+    /// that start at or run through the end of a segment need them to find the intersection.
+    ended: Vec<LineSegment2D>,
+
+    /// The position of every reported intersection in `intersections`, with the start and end
+    /// point as key. The start and end point of an intersection of kind point are equal.
+    reported: BTreeMap<(Point2D, Point2D), usize>,
+
+    /// The position of every reported intersection of kind point of the [exact](crate::exact)
+    /// kernel in `intersections`, with the exact point as key.
+    #[cfg(feature = "exact")]
+    reported_exact: BTreeMap<ExactPoint2D, usize>,
+
+    /// This is the vector of all intersections with the ids of the participating segments.
+    pub intersections: Vec<Intersection>,
+
+    /// The tolerance of comparisons and the snapping of intersection points.
+    tolerance: Tolerance,
 
-    /// This is the vector of all intersecting points.
-    pub intersection_points: Vec<Point2D>,
+    /// Iff `true`, intersections are calculated with the [exact](crate::exact) kernel.
+    #[cfg(feature = "exact")]
+    exact: bool,
 }
 
 impl SweepLine {
     /// Returns a zero initialized `SweepLine` instance.
+    #[allow(clippy::needless_return, clippy::new_without_default)]
     pub fn new() -> SweepLine {
         return SweepLine {
             event_queue: BTreeSet::new(),
-            segments: BTreeMap::new(),
+            segments: YStructure::new(),
             current_event: None,
-            events_order: OrderedFloat(0.0),
             current_x: 0.0,
-            intersection_points: Vec::new(),
+            verticals: Vec::new(),
+            ended: Vec::new(),
+            reported: BTreeMap::new(),
+            #[cfg(feature = "exact")]
+            reported_exact: BTreeMap::new(),
+            intersections: Vec::new(),
+            tolerance: Tolerance::default(),
+            #[cfg(feature = "exact")]
+            exact: false,
         };
     }
+
+    /// Returns a zero initialized `SweepLine` instance with the given
+    /// [tolerance](Tolerance).
+    ///
+    /// Intersection points are snapped to the decimal places of the tolerance and segments run
+    /// through a point, if their y-coordinate is equal within the tolerance.
+    pub fn with_tolerance(tolerance: Tolerance) -> SweepLine {
+        SweepLine {
+            segments: YStructure::with_tolerance(tolerance),
+            tolerance,
+            ..SweepLine::new()
+        }
+    }
+
+    /// Returns a zero initialized `SweepLine` instance, that calculates intersections with the
+    /// [exact](crate::exact) kernel.
+    ///
+    /// The event queue and the y-structure keep the exact intersection points, so events are
+    /// ordered, segments are compared and bundled at exact points without any tolerance. Only
+    /// the reported [intersections](SweepLine::intersections) are converted to the nearest
+    /// [Point2D], so distinct exact points are reported separately even if they round to the
+    /// same point.
+    #[cfg(feature = "exact")]
+    pub fn new_exact() -> SweepLine {
+        SweepLine {
+            exact: true,
+            ..SweepLine::with_tolerance(Tolerance::exact())
+        }
+    }
+
+    /// Inserts the endpoint events of a segment into the event queue.
+    ///
+    /// The [id](LineSegment2D::id) of the segment is used to reference it in the reported
+    /// [intersections](SweepLine::intersections), so it should be unique.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the sweep line uses the [exact](crate::exact) kernel and a
+    /// coordinate is not finite, see [try_insert_segment](SweepLine::try_insert_segment).
+    pub fn insert_segment(&mut self, segment: LineSegment2D) {
+        self.try_insert_segment(segment)
+            .unwrap_or_else(|e| panic!("{e}"));
+    }
+
+    /// Inserts the endpoint events of a segment into the event queue like
+    /// [insert_segment](SweepLine::insert_segment), or returns an error if the sweep line uses
+    /// the [exact](crate::exact) kernel and a coordinate is not finite.
+    pub fn try_insert_segment(&mut self, segment: LineSegment2D) -> Result<(), CgError> {
+        #[cfg(feature = "exact")]
+        let (exact_p1, exact_p2) = if self.exact {
+            (
+                Some(ExactPoint2D::from_point(&segment.p1)?),
+                Some(ExactPoint2D::from_point(&segment.p2)?),
+            )
+        } else {
+            (None, None)
+        };
+        self.event_queue.insert(EventPoint {
+            point: segment.p1,
+            event_type: EventType::IsLeftEndpoint,
+            first_line: segment,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: exact_p1,
+        });
+        self.event_queue.insert(EventPoint {
+            point: segment.p2,
+            event_type: EventType::IsRightEndpoint,
+            first_line: segment,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: exact_p2,
+        });
+        Ok(())
+    }
+
     /// This pops a new event point from the event queue and handles it.
     ///
     /// 1. Pops event form queue.
-    /// 2. Update segments in the y-structure with event's x-coordinate.
+    /// 2. Move the y-structure to the event's point.
     /// 3. Handle event type:
     ///     - LeftEndpoint
     ///     - RightEndpoint
     ///     - Intersection
     ///
+    /// Events of vertical segments are handled separately by
+    /// [process_vertical_event](SweepLine::process_vertical_event).
     pub fn process_next_event(&mut self) {
         let e: EventPoint = self.event_queue.pop_first().unwrap();
 
-        self.current_event = Some(e);
+        if self.leaves_x(&e) {
+            self.verticals.clear();
+            self.ended.clear();
+        }
+        self.current_event = Some(e.clone());
         self.current_x = e.point.x;
-        self.events_order = OrderedFloat(e.first_line.line.y_from_x(self.current_x));
-        self.update_segments();
+        self.move_to(&e);
+        if e.first_line.line().is_vertical() {
+            self.process_vertical_event(e);
+            return;
+        }
 
         match e.event_type {
             EventType::IsLeftEndpoint => {
                 let seg_e = e.first_line;
-                self.segments.insert(self.events_order, seg_e);
-                let seg_a = self.get_next_neighbor(self.events_order);
-                let seg_b = self.get_prev_neighbor(self.events_order);
+                self.segments.insert(seg_e);
+                let seg_a = self.get_next_neighbor(&seg_e);
+                let seg_b = self.get_prev_neighbor(&seg_e);
+
+                // If the segment starts on a vertical segment at the same x-coordinate, on
+                // segments of the y-structure like ones that start at the same point, or at the
+                // end of a segment
+                let mut crossed: Vec<LineSegment2D> = self
+                    .verticals
+                    .iter()
+                    .filter(|v| v.min_y <= e.point.y && e.point.y <= v.max_y)
+                    .copied()
+                    .collect();
+                crossed.extend(self.running_through(&seg_e, &e.point));
+                crossed.extend(self.ended_at(&e.point));
+                self.report_touching(seg_e, crossed, e.point);
 
                 // If the segment above exists and intersects the events segment
                 if let Some(seg_a) = seg_a {
-                    if let Some(intersection) = seg_a.intersects(&seg_e) {
-                        if intersection.x > self.current_x {
-                            self.event_queue.insert(EventPoint {
-                                point: intersection.round(9),
-                                event_type: EventType::IsIntersection,
-                                first_line: seg_e,
-                                second_line: Some(seg_a),
-                            });
-                        }
-                    }
+                    self.check_intersection(seg_e, seg_a);
                 }
 
                 // If the segment below exists and intersects the events segment
                 if let Some(seg_b) = seg_b {
-                    if let Some(intersection) = seg_b.intersects(&seg_e) {
-                        if intersection.x > self.current_x {
-                            self.event_queue.insert(EventPoint {
-                                point: intersection.round(9),
-                                event_type: EventType::IsIntersection,
-                                first_line: seg_e,
-                                second_line: Some(seg_b),
-                            });
-                        }
-                    }
+                    self.check_intersection(seg_e, seg_b);
                 }
             }
             EventType::IsRightEndpoint => {
-                let seg_a = self.get_next_neighbor(self.events_order);
-                let seg_b = self.get_prev_neighbor(self.events_order);
-                self.segments.remove(&self.events_order);
+                // If the segment ends on segments of the y-structure or at the end of another
+                // segment, whose intersection events may already be without a segment left in
+                // the y-structure
+                let mut crossed = self.running_through(&e.first_line, &e.point);
+                crossed.extend(self.ended_at(&e.point));
+                self.report_touching(e.first_line, crossed, e.point);
+
+                let seg_a = self.get_next_neighbor(&e.first_line);
+                let seg_b = self.get_prev_neighbor(&e.first_line);
+                self.segments.remove(&e.first_line);
+                self.ended.push(e.first_line);
 
                 // If the segment above and below both exist and intersects
                 if let (Some(seg_a), Some(seg_b)) = (seg_a, seg_b) {
-                    if let Some(intersection) = seg_a.intersects(&seg_b) {
-                        if intersection.x > self.current_x {
-                            self.event_queue.insert(EventPoint {
-                                point: intersection.round(9),
-                                event_type: EventType::IsIntersection,
-                                first_line: seg_a,
-                                second_line: Some(seg_b),
-                            });
-                        }
-                    }
+                    self.check_intersection(seg_a, seg_b);
                 }
             }
             EventType::IsIntersection => {
-                // println!("Intersection at {} of {} and {}", e.point, e.first_line, e.second_line.unwrap());
-                self.intersection_points.push(e.point);
-                let mut seg_e1 = e.first_line;
-                let mut seg_e2 = e.second_line.unwrap();
-                if seg_e2 > seg_e1 {
-                    (seg_e1, seg_e2) = (seg_e2, seg_e1);
-                } // seg_e1 is now above seg_e2
-                let order_e1 = OrderedFloat(seg_e1.line.y_from_x(self.current_x + 1e-8));
-                let order_e2 = OrderedFloat(seg_e2.line.y_from_x(self.current_x + 1e-8));
-                let seg_a = self.get_prev_neighbor(order_e2);
-                let seg_b = self.get_next_neighbor(order_e1);
-
-                // If the upper segment now has a next neighbor and intersects it
-                if let Some(seg_a) = seg_a {
-                    if let Some(intersection) = seg_a.intersects(&seg_e2) {
-                        if intersection.x > self.current_x {
-                            self.event_queue.insert(EventPoint {
-                                point: intersection.round(9),
-                                event_type: EventType::IsIntersection,
-                                first_line: seg_e2,
-                                second_line: Some(seg_a),
-                            });
+                // The bundle consists of all segments of the y-structure that run through the
+                // point, including the ones that never were neighbors like equal segments. The
+                // segments that end at the point already left the y-structure.
+                let lines = e.lines();
+                let in_bundle = |s: &LineSegment2D| {
+                    lines.iter().any(|l| l.id == s.id) || self.runs_through(s, &e.point)
+                };
+                let mut bundle: Vec<LineSegment2D> = Vec::new();
+                let start = lines
+                    .iter()
+                    .find(|s| self.segments.contains(s))
+                    .copied()
+                    .or_else(|| {
+                        // All segments of the event may have ended at the point already, while
+                        // others still cross there
+                        let above = self.first_from(&e)?;
+                        [Some(above), self.get_prev_neighbor(&above)]
+                            .into_iter()
+                            .flatten()
+                            .find(|s| in_bundle(s))
+                    });
+                if let Some(start) = start {
+                    let mut seg_low = start;
+                    while let Some(seg_a) = self.get_prev_neighbor(&seg_low) {
+                        if !in_bundle(&seg_a) {
+                            break;
                         }
+                        seg_low = seg_a;
                     }
+                    let mut current = Some(seg_low);
+                    while let Some(segment) = current {
+                        if !in_bundle(&segment) {
+                            break;
+                        }
+                        bundle.push(segment);
+                        current = self.get_next_neighbor(&segment);
+                    }
+                }
+                let mut reported = lines.clone();
+                reported.extend(bundle.iter().copied());
+                reported.extend(self.ended_at(&e.point));
+                self.report_event(&e, &reported);
+
+                if bundle.is_empty() {
+                    return;
+                }
+
+                // Reverse the bundle to its order right of the point
+                self.segments.reorder(&mut bundle);
+                let seg_low = bundle[0];
+                let seg_high = bundle[bundle.len() - 1];
+                let seg_a = self.get_prev_neighbor(&seg_low);
+                let seg_b = self.get_next_neighbor(&seg_high);
+
+                // If the lowest segment now has a previous neighbor and intersects it
+                if let Some(seg_a) = seg_a {
+                    self.check_intersection(seg_low, seg_a);
                 }
 
-                // If the lower segment now has a previous neighbor and intersects it
+                // If the highest segment now has a next neighbor and intersects it
                 if let Some(seg_b) = seg_b {
-                    if let Some(intersection) = seg_b.intersects(&seg_e1) {
-                        if intersection.x > self.current_x {
-                            self.event_queue.insert(EventPoint {
-                                point: intersection.round(9),
-                                event_type: EventType::IsIntersection,
-                                first_line: seg_b,
-                                second_line: Some(seg_e1),
-                            });
-                        }
-                    }
+                    self.check_intersection(seg_b, seg_high);
+                }
+            }
+        }
+    }
+
+    /// Inserts an intersection event if two neighbors [still cross](SweepLine::still_cross).
+    ///
+    /// If there is already an intersection event at the same point, both segments are added to
+    /// the bundle of this event instead, so that the point is handled only once. Colinear
+    /// segments that overlap are reported immediately as an overlap, since the overlap starts at
+    /// the current event at the latest.
+    fn check_intersection(&mut self, seg_a: LineSegment2D, seg_b: LineSegment2D) {
+        let intersection = match self.intersect(&seg_a, &seg_b) {
+            SegmentIntersection::Point(intersection) if self.still_cross(&seg_a, &seg_b) => {
+                intersection
+            }
+            SegmentIntersection::Overlap(overlap) => {
+                let kind = IntersectionKind::Overlap(overlap);
+                self.report_intersection(overlap.p1, kind, &[seg_a, seg_b]);
+                return;
+            }
+            _ => return,
+        };
+
+        let mut event = EventPoint {
+            point: self.event_point(intersection),
+            event_type: EventType::IsIntersection,
+            first_line: seg_a,
+            other_lines: vec![seg_b],
+            #[cfg(feature = "exact")]
+            exact: None,
+        };
+        #[cfg(feature = "exact")]
+        if self.exact {
+            // The exact point orders the event, it is only rounded for the output
+            if let ExactIntersection::Point(p) = exact::intersection(&seg_a, &seg_b).unwrap() {
+                event.point = p.to_point();
+                event.exact = Some(p);
+            }
+        }
+        if let Some(existing) = self.event_queue.take(&event) {
+            event = existing;
+            for segment in [seg_a, seg_b] {
+                if !event.lines().iter().any(|s| s.id == segment.id) {
+                    event.other_lines.push(segment);
                 }
             }
         }
+        self.event_queue.insert(event);
+    }
+
+    /// Returns the intersection of two segments with the kernel of the sweep line.
+    fn intersect(&self, seg_a: &LineSegment2D, seg_b: &LineSegment2D) -> SegmentIntersection {
+        #[cfg(feature = "exact")]
+        if self.exact {
+            // The coordinates are finite, since they were checked on insertion
+            return match exact::intersection(seg_a, seg_b).unwrap() {
+                ExactIntersection::None => SegmentIntersection::None,
+                ExactIntersection::Point(p) => SegmentIntersection::Point(p.to_point()),
+                ExactIntersection::Overlap(p, q) => {
+                    SegmentIntersection::Overlap(LineSegment2D::new(p.to_point(), q.to_point()))
+                }
+            };
+        }
+        seg_a.intersects(seg_b)
     }
 
-    /// This rearranges all line segments in the `segments` map.
+    /// Returns `true` iff two neighbors in the y-structure still have to cross right of the sweep
+    /// line, which is the case if the lower one ends above the upper one or the upper one ends
+    /// below the lower one.
     ///
-    /// The rearranging works by calculating every y-coordinate of each line segment with the
-    /// current x-coordinate of the sweep line. In the case of an intersection, where two lines
-    /// would have the same y-coordinate, a small epsilon value is added to retrieve the position
-    /// after the intersection x-coordinate.
-    pub fn update_segments(&mut self) {
-        let epsilon = if self.current_event.unwrap().event_type != EventType::IsIntersection {
-            0.0
+    /// This is decided exactly with [orient2d], since the intersection point is snapped or
+    /// rounded, which may move it onto the sweep line.
+    fn still_cross(&self, seg_a: &LineSegment2D, seg_b: &LineSegment2D) -> bool {
+        let (low, high) = if self.get_next_neighbor(seg_a).map(|s| s.id) == Some(seg_b.id) {
+            (seg_a, seg_b)
         } else {
-            1e-8
+            (seg_b, seg_a)
         };
+        orient2d(&high.p1, &high.p2, &low.p2) > 0.0 || orient2d(&low.p1, &low.p2, &high.p2) < 0.0
+    }
 
-        let mut temp_map: BTreeMap<OrderedFloat<f64>, LineSegment2D> = BTreeMap::new();
+    /// Returns the point of an intersection event.
+    ///
+    /// Points are snapped to the decimal places of the [tolerance](Tolerance), so that the same
+    /// point of different pairs of segments matches. A crossing right next to the sweep line may
+    /// still end up left of it, so it is moved onto it. The [exact](crate::exact) kernel replaces
+    /// this point by the exact one.
+    fn event_point(&self, p: Point2D) -> Point2D {
+        let p = self.tolerance.snap(p);
+        Point2D {
+            x: p.x.max(self.current_x),
+            y: p.y,
+        }
+    }
 
-        for (&_key, &value) in &self.segments {
-            let updated_key = OrderedFloat(value.line.y_from_x(self.current_x + epsilon));
-            temp_map.insert(updated_key, value);
+    /// This handles an event of a vertical segment.
+    ///
+    /// A vertical segment is never inserted into the y-structure. At its left endpoint, which is
+    /// the lower one, it reports an intersection with every segment of the y-structure whose
+    /// y-coordinate at the current x-coordinate lies within the range of the vertical segment, as
+    /// well as with every other vertical segment it touches and every segment that ended on it.
+    /// Afterwards it stays active until the
+    /// sweep line leaves its x-coordinate, so that segments starting on it are reported as well.
+    ///
+    /// A [degenerate](LineSegment2D::is_degenerate) segment is handled like a vertical segment of
+    /// zero length. The end of the range is therefore decided exactly with
+    /// [orient2d], since a rounded y-coordinate could miss a single point.
+    pub fn process_vertical_event(&mut self, e: EventPoint) {
+        if e.event_type != EventType::IsLeftEndpoint {
+            return;
         }
 
-        std::mem::swap(&mut self.segments, &mut temp_map);
+        let vertical = e.first_line;
+        let mut crossed: Vec<(f64, LineSegment2D)> = Vec::new();
+        let mut current = self.segments.first_from(vertical.min_y);
+        while let Some(segment) = current {
+            if orient2d(&segment.p1, &segment.p2, &vertical.p2) < 0.0 {
+                break;
+            }
+            let y = self.segments.y_of(&segment);
+            crossed.push((y.clamp(vertical.min_y, vertical.max_y), segment));
+            current = self.get_next_neighbor(&segment);
+        }
+        for (y, segment) in crossed {
+            #[cfg(feature = "exact")]
+            if let Some(position) = self.current_exact() {
+                let (bottom, top) = exact::endpoints(&vertical);
+                let y = exact::y_at(&segment, &position.x).clamp(bottom.y, top.y);
+                let point = ExactPoint2D {
+                    x: position.x.clone(),
+                    y,
+                };
+                self.report_exact_point(point, &[vertical, segment]);
+                continue;
+            }
+            let point = Point2D {
+                x: self.current_x,
+                y,
+            };
+            self.report_intersection(
+                self.tolerance.snap(point),
+                IntersectionKind::Point,
+                &[vertical, segment],
+            );
+        }
+
+        let touched: Vec<(SegmentIntersection, LineSegment2D)> = self
+            .verticals
+            .iter()
+            .map(|v| (v.intersects(&vertical), *v))
+            .chain(
+                self.ended
+                    .iter()
+                    .filter(|s| vertical.contains(&s.p2))
+                    .map(|s| (SegmentIntersection::Point(s.p2), *s)),
+            )
+            .collect();
+        for (intersection, other) in touched {
+            match intersection {
+                SegmentIntersection::Point(point) => {
+                    self.report_intersection(point, IntersectionKind::Point, &[other, vertical]);
+                }
+                SegmentIntersection::Overlap(overlap) => {
+                    let kind = IntersectionKind::Overlap(overlap);
+                    self.report_intersection(overlap.p1, kind, &[other, vertical]);
+                }
+                SegmentIntersection::None => {}
+            }
+        }
+        self.verticals.push(vertical);
+    }
+
+    /// Reports the intersections of a segment with other segments that touch it at a point of the
+    /// sweep line.
+    ///
+    /// Colinear segments are reported as an overlap, which starts at the point at the latest.
+    fn report_touching(&mut self, segment: LineSegment2D, others: Vec<LineSegment2D>, p: Point2D) {
+        for other in others {
+            match self.intersect(&segment, &other) {
+                SegmentIntersection::Point(_) => {
+                    self.report_intersection(p, IntersectionKind::Point, &[other, segment]);
+                }
+                SegmentIntersection::Overlap(overlap) => {
+                    let kind = IntersectionKind::Overlap(overlap);
+                    self.report_intersection(overlap.p1, kind, &[other, segment]);
+                }
+                SegmentIntersection::None => {}
+            }
+        }
+    }
+
+    /// Adds an intersection of the given segments at a point to the reported intersections.
+    ///
+    /// If the point was already reported, the segments are merged into the existing
+    /// intersection, so that every point is reported once with every segment through it. The
+    /// same applies to overlaps with equal start and end point.
+    fn report_intersection(
+        &mut self,
+        point: Point2D,
+        kind: IntersectionKind,
+        lines: &[LineSegment2D],
+    ) {
+        #[cfg(feature = "exact")]
+        if self.exact && kind == IntersectionKind::Point {
+            // The coordinates are finite, since they were checked on insertion
+            let point = ExactPoint2D::from_point(&point).unwrap();
+            self.report_exact_point(point, lines);
+            return;
+        }
+        let end = match kind {
+            IntersectionKind::Point => point,
+            IntersectionKind::Overlap(overlap) => overlap.p2,
+        };
+        let position = *self.reported.entry((point, end)).or_insert_with(|| {
+            self.intersections.push(Intersection {
+                point,
+                segments: Vec::new(),
+                kind,
+            });
+            self.intersections.len() - 1
+        });
+        self.add_segments(position, lines);
+    }
+
+    /// Adds an intersection of the given segments at an exact point of the
+    /// [exact](crate::exact) kernel, which is only rounded for the reported intersection, see
+    /// [report_intersection](SweepLine::report_intersection).
+    #[cfg(feature = "exact")]
+    fn report_exact_point(&mut self, point: ExactPoint2D, lines: &[LineSegment2D]) {
+        let position = *self
+            .reported_exact
+            .entry(point)
+            .or_insert_with_key(|point| {
+                self.intersections.push(Intersection {
+                    point: point.to_point(),
+                    segments: Vec::new(),
+                    kind: IntersectionKind::Point,
+                });
+                self.intersections.len() - 1
+            });
+        self.add_segments(position, lines);
+    }
+
+    /// Reports the intersection of the segments at the point of an intersection event.
+    fn report_event(&mut self, e: &EventPoint, lines: &[LineSegment2D]) {
+        #[cfg(feature = "exact")]
+        if let Some(point) = e.exact.clone() {
+            self.report_exact_point(point, lines);
+            return;
+        }
+        self.report_intersection(e.point, IntersectionKind::Point, lines);
+    }
+
+    /// Adds the ids of the given segments to a reported intersection.
+    fn add_segments(&mut self, position: usize, lines: &[LineSegment2D]) {
+        let segments = &mut self.intersections[position].segments;
+        segments.extend(lines.iter().map(|s| s.id));
+        segments.sort_unstable();
+        segments.dedup();
+    }
+
+    /// Returns `true` iff an event lies right of the x-coordinate of the current event, which is
+    /// compared exactly for events of the [exact](crate::exact) kernel.
+    fn leaves_x(&self, e: &EventPoint) -> bool {
+        #[cfg(feature = "exact")]
+        if let (Some(p), Some(current)) = (&e.exact, self.current_exact()) {
+            return p.x != current.x;
+        }
+        e.point.x != self.current_x
+    }
+
+    /// Moves the y-structure to the point of an event, or to its exact point.
+    fn move_to(&mut self, e: &EventPoint) {
+        #[cfg(feature = "exact")]
+        if let Some(p) = e.exact.clone() {
+            self.segments.set_exact_position(p);
+            return;
+        }
+        self.segments.set_position(e.point);
+    }
+
+    /// Returns the lowest segment of the y-structure that runs through or above the point of an
+    /// event.
+    fn first_from(&self, e: &EventPoint) -> Option<LineSegment2D> {
+        #[cfg(feature = "exact")]
+        if let Some(p) = &e.exact {
+            return self.segments.first_from_exact(p);
+        }
+        self.segments.first_from(e.point.y)
+    }
+
+    /// Returns the exact point of the current event, if the sweep line uses the
+    /// [exact](crate::exact) kernel.
+    #[cfg(feature = "exact")]
+    fn current_exact(&self) -> Option<&ExactPoint2D> {
+        self.current_event.as_ref()?.exact.as_ref()
+    }
+
+    /// Returns the segments of the y-structure around a segment that run exactly through a point.
+    ///
+    /// These segments all have the y-coordinate of the point, so they are neighbors of the segment
+    /// or of each other. Their intersection is not right of the sweep line, so it would not be
+    /// found as an intersection event.
+    fn running_through(&self, segment: &LineSegment2D, p: &Point2D) -> Vec<LineSegment2D> {
+        let mut found = Vec::new();
+        for step in [SweepLine::get_next_neighbor, SweepLine::get_prev_neighbor] {
+            let mut current = step(self, segment);
+            while let Some(other) = current {
+                if !other.contains(p) {
+                    break;
+                }
+                found.push(other);
+                current = step(self, &other);
+            }
+        }
+        found
+    }
+
+    /// Returns the segments that ended at a point of the sweep line.
+    ///
+    /// The y-coordinates are compared with the [tolerance](Tolerance) like in
+    /// [runs_through](SweepLine::runs_through). The [exact](crate::exact) kernel compares the end
+    /// points to the exact point of the current event instead.
+    fn ended_at(&self, p: &Point2D) -> Vec<LineSegment2D> {
+        #[cfg(feature = "exact")]
+        if let Some(exact) = self.current_exact() {
+            return self
+                .ended
+                .iter()
+                .filter(|s| exact::endpoints(s).1 == *exact)
+                .copied()
+                .collect();
+        }
+        self.ended
+            .iter()
+            .filter(|s| s.p2.x == p.x && self.tolerance.is_equal(s.p2.y, p.y))
+            .copied()
+            .collect()
+    }
+
+    /// Returns `true` iff a non vertical segment runs through a point of the sweep line.
+    ///
+    /// The y-coordinates are compared with the [tolerance](Tolerance), since intersection points
+    /// are snapped. The [exact](crate::exact) kernel decides exactly whether the segment runs
+    /// through the exact point of the current event.
+    fn runs_through(&self, segment: &LineSegment2D, p: &Point2D) -> bool {
+        #[cfg(feature = "exact")]
+        if let Some(exact) = self.current_exact() {
+            let (p1, p2) = exact::endpoints(segment);
+            return exact::ccw(&p1, &p2, exact).is_zero();
+        }
+        let y = segment.line().y_from_x(p.x).unwrap();
+        self.tolerance.is_equal(y, p.y)
     }
 
     /// This enables a print of the current state of the sweep line segments.
     pub fn print(&self) {
         println!("\nCurrent x: {}", self.current_x);
-        println!("Current event: {}", self.current_event.unwrap());
-        println!("Current key: {}", self.events_order);
-        for (key, value) in &self.segments {
-            println!("( key: {} , slope: {} )", key, value.line.slope);
+        println!("Current event: {}", self.current_event.as_ref().unwrap());
+        for segment in self.segments.segments() {
+            println!(
+                "( y: {} , slope: {} , id: {} )",
+                self.segments.y_of(&segment),
+                segment.line().slope().unwrap(),
+                segment.id
+            );
         }
     }
 
-    /// Returns the next segment neighbor of a given key value in the y-structure.
-    pub fn get_next_neighbor(&self, key: OrderedFloat<f64>) -> Option<LineSegment2D> {
-        let next = self.segments.range((Excluded(&key), Unbounded)).next();
-        if let Some((_next_key, next_value)) = next {
-            return Some(*next_value);
-        } else {
-            return None;
-        }
+    /// Returns the next segment neighbor of a given segment in the y-structure.
+    pub fn get_next_neighbor(&self, segment: &LineSegment2D) -> Option<LineSegment2D> {
+        self.segments.next(segment)
     }
 
-    /// Returns the previous segment neighbor of a given key value in the y-structure.
-    pub fn get_prev_neighbor(&self, key: OrderedFloat<f64>) -> Option<LineSegment2D> {
-        let prev = self.segments.range((Unbounded, Excluded(&key))).next_back();
-        if let Some((_prev_key, prev_value)) = prev {
-            return Some(*prev_value);
-        } else {
-            return None;
-        }
+    /// Returns the previous segment neighbor of a given segment in the y-structure.
+    pub fn get_prev_neighbor(&self, segment: &LineSegment2D) -> Option<LineSegment2D> {
+        self.segments.prev(segment)
     }
 }
 
 #[cfg(test)]
 mod test_sweep_line {
     use super::*;
+    use crate::tools2d::splitmix64;
+    use std::collections::BTreeSet;
+
+    /// Returns the pairs of segment ids that are reported to intersect by the sweep line.
+    fn swept_pairs(segments: &[LineSegment2D]) -> BTreeSet<(usize, usize)> {
+        pairs_of(SweepLine::new(), segments)
+    }
+
+    /// Returns the pairs of segment ids that are reported to intersect by a given sweep line.
+    fn pairs_of(mut sl: SweepLine, segments: &[LineSegment2D]) -> BTreeSet<(usize, usize)> {
+        for (id, segment) in segments.iter().enumerate() {
+            sl.insert_segment(segment.with_id(id));
+        }
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+        let mut pairs = BTreeSet::new();
+        for intersection in &sl.intersections {
+            for (k, &i) in intersection.segments.iter().enumerate() {
+                for &j in &intersection.segments[k + 1..] {
+                    pairs.insert((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Returns the pairs of segment ids that intersect by a pairwise check.
+    fn brute_force_pairs(segments: &[LineSegment2D]) -> BTreeSet<(usize, usize)> {
+        let mut pairs = BTreeSet::new();
+        for i in 0..segments.len() {
+            for j in (i + 1)..segments.len() {
+                if segments[i].intersects(&segments[j]) != SegmentIntersection::None {
+                    pairs.insert((i, j));
+                }
+            }
+        }
+        pairs
+    }
 
     //#[test]
     fn test_update() {
@@ -234,13 +739,17 @@ mod test_sweep_line {
             point: s1.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e1_2: EventPoint = EventPoint {
             point: s1.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         let p1: Point2D = Point2D { x: 0.0, y: 0.0 };
@@ -250,13 +759,17 @@ mod test_sweep_line {
             point: s2.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s2,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e2_2: EventPoint = EventPoint {
             point: s2.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s2,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         let p1: Point2D = Point2D { x: 1.0, y: 1.0 };
@@ -264,7 +777,9 @@ mod test_sweep_line {
             point: p1,
             event_type: EventType::IsIntersection,
             first_line: s1,
-            second_line: Some(s2),
+            other_lines: vec![s2],
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         sl.event_queue.extend(vec![e1_1, e1_2, e2_1, e2_2, e12]);
@@ -284,13 +799,17 @@ mod test_sweep_line {
             point: s1.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e1_2: EventPoint = EventPoint {
             point: s1.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s1,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         let p1: Point2D = Point2D { x: 0.0, y: 0.0 };
@@ -300,13 +819,17 @@ mod test_sweep_line {
             point: s2.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s2,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e2_2: EventPoint = EventPoint {
             point: s2.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s2,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         let p1: Point2D = Point2D { x: -1.5, y: 4.0 };
@@ -316,13 +839,17 @@ mod test_sweep_line {
             point: s3.p1,
             event_type: EventType::IsLeftEndpoint,
             first_line: s3,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
         let e3_2: EventPoint = EventPoint {
             point: s3.p2,
             event_type: EventType::IsRightEndpoint,
             first_line: s3,
-            second_line: None,
+            other_lines: Vec::new(),
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         let p1: Point2D = Point2D { x: 1.0, y: 1.0 };
@@ -330,7 +857,9 @@ mod test_sweep_line {
             point: p1,
             event_type: EventType::IsIntersection,
             first_line: s1,
-            second_line: Some(s2),
+            other_lines: vec![s2],
+            #[cfg(feature = "exact")]
+            exact: None,
         };
 
         sl.event_queue
@@ -339,4 +868,432 @@ mod test_sweep_line {
             sl.process_next_event();
         }
     }
+
+    #[test]
+    fn test_vertical() {
+        let mut sl: SweepLine = SweepLine::new();
+
+        let s0: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 4.0 });
+        let s1: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 3.0 }, Point2D { x: 4.0, y: 3.0 });
+        let s2: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 2.0, y: 5.0 }, Point2D { x: 2.0, y: -1.0 });
+        let s3: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 2.0, y: 4.0 }, Point2D { x: 5.0, y: 6.0 });
+        let s4: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 3.5, y: 0.0 }, Point2D { x: 3.5, y: 1.0 });
+        sl.insert_segment(s0.with_id(0));
+        sl.insert_segment(s1.with_id(1));
+        sl.insert_segment(s2.with_id(2));
+        sl.insert_segment(s3.with_id(3));
+        sl.insert_segment(s4.with_id(4));
+
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+        sl.intersections.sort();
+
+        assert_eq!(
+            vec![
+                Intersection {
+                    point: Point2D { x: 2.0, y: 2.0 },
+                    segments: vec![0, 2],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 2.0, y: 3.0 },
+                    segments: vec![1, 2],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 2.0, y: 4.0 },
+                    segments: vec![2, 3],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 3.0, y: 3.0 },
+                    segments: vec![0, 1],
+                    kind: IntersectionKind::Point,
+                },
+            ],
+            sl.intersections
+        );
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let mut sl: SweepLine = SweepLine::new();
+
+        // s1 lies on s0, s2 is the start of s3 and s4 lies on no other segment
+        let s0: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 1.0 });
+        let s1: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 1.0, y: 0.25 }, Point2D { x: 1.0, y: 0.25 });
+        let s2: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 2.0, y: 1.0 }, Point2D { x: 2.0, y: 1.0 });
+        let s3: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 2.0, y: 1.0 }, Point2D { x: 3.0, y: 2.0 });
+        let s4: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 1.0, y: 1.0 }, Point2D { x: 1.0, y: 1.0 });
+        sl.insert_segment(s0.with_id(0));
+        sl.insert_segment(s1.with_id(1));
+        sl.insert_segment(s2.with_id(2));
+        sl.insert_segment(s3.with_id(3));
+        sl.insert_segment(s4.with_id(4));
+
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+        sl.intersections.sort();
+
+        assert_eq!(
+            vec![
+                Intersection {
+                    point: Point2D { x: 1.0, y: 0.25 },
+                    segments: vec![0, 1],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 2.0, y: 1.0 },
+                    segments: vec![2, 3],
+                    kind: IntersectionKind::Point,
+                },
+            ],
+            sl.intersections
+        );
+    }
+
+    #[test]
+    fn test_touching() {
+        let mut sl: SweepLine = SweepLine::new();
+
+        // s1 starts on the interior of s0, s2 and s3 start at the same point, the vertical s4
+        // starts at the end of s0 and s5 starts at the end of s3
+        let s0: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 0.0 });
+        let s1: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 1.0, y: 0.0 }, Point2D { x: 2.0, y: 1.0 });
+        let s2: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 1.0, y: 3.0 });
+        let s3: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 1.0, y: 1.5 });
+        sl.insert_segment(s0.with_id(0));
+        sl.insert_segment(s1.with_id(1));
+        sl.insert_segment(s2.with_id(2));
+        sl.insert_segment(s3.with_id(3));
+        let s4: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 2.0, y: 0.0 }, Point2D { x: 2.0, y: 0.5 });
+        sl.insert_segment(s4.with_id(4));
+        let s5: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 1.0, y: 1.5 }, Point2D { x: 2.0, y: 2.5 });
+        sl.insert_segment(s5.with_id(5));
+
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+        sl.intersections.sort();
+
+        assert_eq!(
+            vec![
+                Intersection {
+                    point: Point2D { x: 0.0, y: 2.0 },
+                    segments: vec![2, 3],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 1.0, y: 0.0 },
+                    segments: vec![0, 1],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 1.0, y: 1.5 },
+                    segments: vec![3, 5],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 2.0, y: 0.0 },
+                    segments: vec![0, 4],
+                    kind: IntersectionKind::Point,
+                },
+            ],
+            sl.intersections
+        );
+    }
+
+    #[test]
+    fn test_bundle() {
+        let mut sl: SweepLine = SweepLine::new();
+
+        let s0: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 6.0, y: 6.0 });
+        let s1: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 6.0, y: 2.0 });
+        let s2: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 4.0 }, Point2D { x: 6.0, y: -2.0 });
+        let s3: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: -1.0 }, Point2D { x: 6.0, y: -1.0 });
+        sl.insert_segment(s0.with_id(0));
+        sl.insert_segment(s1.with_id(1));
+        sl.insert_segment(s2.with_id(2));
+        sl.insert_segment(s3.with_id(3));
+
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+
+        // The bundle is reported once and s2 becomes the neighbor of s3 after the bundle
+        assert_eq!(
+            vec![
+                Intersection {
+                    point: Point2D { x: 2.0, y: 2.0 },
+                    segments: vec![0, 1, 2],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D { x: 5.0, y: -1.0 },
+                    segments: vec![2, 3],
+                    kind: IntersectionKind::Point,
+                },
+            ],
+            sl.intersections
+        );
+    }
+
+    #[test]
+    fn test_overlap() {
+        let mut sl: SweepLine = SweepLine::new();
+
+        let s0: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 4.0 });
+        let s1: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 2.0, y: 2.0 }, Point2D { x: 6.0, y: 6.0 });
+        let s2: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 7.0, y: 0.0 }, Point2D { x: 7.0, y: 2.0 });
+        let s3: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 7.0, y: 1.0 }, Point2D { x: 7.0, y: 3.0 });
+        sl.insert_segment(s0.with_id(0));
+        sl.insert_segment(s1.with_id(1));
+        sl.insert_segment(s2.with_id(2));
+        sl.insert_segment(s3.with_id(3));
+
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+
+        let overlap_01 = LineSegment2D::new(Point2D { x: 2.0, y: 2.0 }, Point2D { x: 4.0, y: 4.0 });
+        let overlap_23 = LineSegment2D::new(Point2D { x: 7.0, y: 1.0 }, Point2D { x: 7.0, y: 2.0 });
+        assert_eq!(
+            vec![
+                Intersection {
+                    point: overlap_01.p1,
+                    segments: vec![0, 1],
+                    kind: IntersectionKind::Overlap(overlap_01),
+                },
+                Intersection {
+                    point: overlap_23.p1,
+                    segments: vec![2, 3],
+                    kind: IntersectionKind::Overlap(overlap_23),
+                },
+            ],
+            sl.intersections
+        );
+    }
+
+    #[test]
+    fn test_ids() {
+        let mut sl: SweepLine = SweepLine::new();
+
+        // s0 and s1 are equal, s2 starts at the same point as both
+        let s0: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 4.0 });
+        let s1: LineSegment2D = s0;
+        let s2: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 2.0 });
+        let s3: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 4.0 }, Point2D { x: 4.0, y: 0.0 });
+        sl.insert_segment(s0.with_id(0));
+        sl.insert_segment(s1.with_id(1));
+        sl.insert_segment(s2.with_id(2));
+        sl.insert_segment(s3.with_id(3));
+        assert_eq!(8, sl.event_queue.len());
+
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+        sl.intersections.sort();
+
+        // s2 touches s0 and s1 at their common start, s0 and s1 overlap completely
+        assert_eq!(
+            vec![
+                Intersection {
+                    point: s0.p1,
+                    segments: vec![0, 1, 2],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: s0.p1,
+                    segments: vec![0, 1],
+                    kind: IntersectionKind::Overlap(s0),
+                },
+                Intersection {
+                    point: Point2D { x: 2.0, y: 2.0 },
+                    segments: vec![0, 1, 3],
+                    kind: IntersectionKind::Point,
+                },
+                Intersection {
+                    point: Point2D {
+                        x: 8.0 / 3.0,
+                        y: 4.0 / 3.0
+                    }
+                    .round(9),
+                    segments: vec![2, 3],
+                    kind: IntersectionKind::Point,
+                },
+            ],
+            sl.intersections
+        );
+    }
+
+    #[test]
+    fn test_intersections() {
+        let mut sl: SweepLine = SweepLine::new();
+
+        let s0: LineSegment2D =
+            LineSegment2D::new(Point2D { x: -1.0, y: 3.0 }, Point2D { x: 3.0, y: -1.0 });
+        let s1: LineSegment2D =
+            LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let s2: LineSegment2D =
+            LineSegment2D::new(Point2D { x: -1.5, y: 4.0 }, Point2D { x: 4.0, y: 5.0 });
+        sl.insert_segment(s0.with_id(0));
+        sl.insert_segment(s1.with_id(1));
+        sl.insert_segment(s2.with_id(2));
+
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+
+        assert_eq!(
+            vec![Intersection {
+                point: Point2D { x: 1.0, y: 1.0 },
+                segments: vec![0, 1],
+                kind: IntersectionKind::Point,
+            }],
+            sl.intersections
+        );
+    }
+
+    #[test]
+    fn test_endpoints() {
+        let segment = |x1: f64, y1: f64, x2: f64, y2: f64| {
+            LineSegment2D::new(Point2D { x: x1, y: y1 }, Point2D { x: x2, y: y2 })
+        };
+
+        // s1 starts where s0 ends
+        let segments = vec![segment(0.0, 0.0, 1.0, 1.0), segment(1.0, 1.0, 2.0, 0.0)];
+        assert_eq!(brute_force_pairs(&segments), swept_pairs(&segments));
+
+        // s2 ends at the crossing of s0 and s1
+        let segments = vec![
+            segment(0.0, 1.0, 3.0, 1.0),
+            segment(1.0, 0.0, 3.0, 2.0),
+            segment(1.0, 1.0, 2.0, 1.0),
+        ];
+        assert_eq!(brute_force_pairs(&segments), swept_pairs(&segments));
+
+        // s1 ends on the interior of s0
+        let segments = vec![segment(2.0, 3.0, 4.0, 1.0), segment(2.0, 5.0, 3.0, 2.0)];
+        assert_eq!(brute_force_pairs(&segments), swept_pairs(&segments));
+
+        // s1 starts next to s0 and crosses it at once, the snapped point is on the sweep line
+        let segments = vec![
+            segment(0.74, 6.66, 13.32, 13.32),
+            segment(7.03, 9.99, 16.65, 16.65),
+        ];
+        assert_eq!(brute_force_pairs(&segments), swept_pairs(&segments));
+    }
+
+    #[cfg(feature = "exact")]
+    #[test]
+    fn test_exact() {
+        let segment = |x1: f64, y1: f64, x2: f64, y2: f64| {
+            LineSegment2D::new(Point2D { x: x1, y: y1 }, Point2D { x: x2, y: y2 })
+        };
+        // s1 crosses s2 slightly right of the start of s2, which is the rounded point. Without
+        // swapping them there, the crossing of s1 and s3 is missed.
+        let segments = vec![
+            segment(0.0, 0.1, 0.2, 0.4),
+            segment(0.1, 0.1 + 0.2, 0.1 + 0.2, 0.5),
+            segment(0.2, 0.4, 0.5, 0.4),
+            segment(0.2, 0.6, 0.4, 0.1),
+        ];
+        let mut expected = BTreeSet::new();
+        for i in 0..segments.len() {
+            for j in (i + 1)..segments.len() {
+                if exact::intersection(&segments[i], &segments[j]).unwrap()
+                    != ExactIntersection::None
+                {
+                    expected.insert((i, j));
+                }
+            }
+        }
+        assert!(expected.contains(&(1, 3)));
+        assert_eq!(expected, pairs_of(SweepLine::new_exact(), &segments));
+
+        // s0 crosses s1 exactly at (0.5, 0.5) and s2 slightly right of it, which both round to
+        // the same point. The exact events stay apart, only the rounded output points are equal.
+        let segments = vec![
+            segment(0.0, 0.0, 1.0, 1.0),
+            segment(0.0, 1.0, 1.0, 0.0).with_id(1),
+            segment(0.0, 1.0, 1.0 + f64::EPSILON, 0.0).with_id(2),
+        ];
+        let mut sl = SweepLine::new_exact();
+        for segment in segments {
+            sl.insert_segment(segment);
+        }
+        while !sl.event_queue.is_empty() {
+            sl.process_next_event();
+        }
+        let at_middle: Vec<Vec<usize>> = sl
+            .intersections
+            .iter()
+            .filter(|i| i.point == Point2D { x: 0.5, y: 0.5 })
+            .map(|i| i.segments.clone())
+            .collect();
+        assert_eq!(vec![vec![0, 1], vec![0, 2]], at_middle);
+    }
+
+    #[test]
+    fn test_brute_force() {
+        // A reproducible generator without dependencies
+        let mut state: u64 = 0x2545F4914F6CDD1D;
+        let mut next = |n: u64| (splitmix64(&mut state) % n) as f64;
+
+        // Small grids produce many shared endpoints, colinear and vertical segments
+        for (grid, count) in [(4, 4), (4, 8), (20, 12)] {
+            for _ in 0..500 {
+                let segments: Vec<LineSegment2D> = (0..count)
+                    .map(|_| {
+                        let p = Point2D {
+                            x: next(grid),
+                            y: next(grid),
+                        };
+                        let q = Point2D {
+                            x: next(grid),
+                            y: next(grid),
+                        };
+                        LineSegment2D::new(p, q)
+                    })
+                    .collect();
+                let expected = brute_force_pairs(&segments);
+                assert_eq!(expected, swept_pairs(&segments), "{segments:?}");
+                #[cfg(feature = "exact")]
+                assert_eq!(
+                    expected,
+                    pairs_of(SweepLine::new_exact(), &segments),
+                    "{segments:?}"
+                );
+            }
+        }
+    }
 }
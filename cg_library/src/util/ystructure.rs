@@ -0,0 +1,572 @@
+//! This is the ordered set of segments along the sweep line.
+//!
+//! Especially usefull as the y-structure of the [bently ottmann](crate::tools2d::bently_ottmann)
+//! algorithm, where the order of the segments changes with the position of the sweep line.
+
+#[cfg(feature = "exact")]
+use crate::exact::{self, ExactPoint2D};
+use crate::linesegment2d::LineSegment2D;
+use crate::point2d::Point2D;
+use crate::scalar::Scalar;
+use crate::tolerance::Tolerance;
+use crate::tools2d::{orient2d, splitmix64};
+#[cfg(feature = "exact")]
+use num_traits::Signed;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// A node of the tree, it holds one segment.
+#[derive(Debug, Clone)]
+struct Node {
+    /// The segment of the node.
+    segment: LineSegment2D,
+
+    /// The heap priority of the node, derived from the id of the segment it was inserted with.
+    priority: u64,
+
+    /// The parent node, `None` for the root.
+    parent: Option<usize>,
+
+    /// The left child, which holds segments below this one.
+    left: Option<usize>,
+
+    /// The right child, which holds segments above this one.
+    right: Option<usize>,
+}
+
+/// The y-structure of a sweep line, a balanced binary tree of segments.
+///
+/// The segments are not ordered by a stored key, but by a comparator that is evaluated at the
+/// current [position](YStructure::set_position) of the sweep line. Therefore moving the sweep line
+/// does not require to rebuild the tree, as long as no two segments swap between two positions.
+/// Segments that swap at an intersection are [reordered](YStructure::reorder) in their nodes.
+///
+/// The tree is a treap, where the priority of a node is a hash of the segment
+/// [id](LineSegment2D::id). Every node knows its parent and every segment id knows its node, so
+/// that insert, remove and the neighbor lookup each run in expected $O(\log n)$.
+///
+/// Vertical segments are not supported, since they have no single y-coordinate at the sweep line.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::linesegment2d::LineSegment2D;
+/// use cg_library::point2d::Point2D;
+/// use cg_library::util::ystructure::YStructure;
+///
+/// let s0 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+/// let s1 = LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 2.0, y: 0.0 }).with_id(1);
+/// let mut ys: YStructure = YStructure::new();
+/// ys.insert(s0);
+/// ys.insert(s1);
+/// assert_eq!(Some(s1), ys.next(&s0));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct YStructure {
+    /// All nodes of the tree, removed nodes are reused.
+    nodes: Vec<Node>,
+
+    /// The indices of removed nodes.
+    free: Vec<usize>,
+
+    /// The root node of the tree.
+    root: Option<usize>,
+
+    /// The node of every segment id in the tree.
+    node_of: HashMap<usize, usize>,
+
+    /// The current position of the sweep line.
+    position: Point2D,
+
+    /// The tolerance of y-coordinates at the sweep line.
+    tolerance: Tolerance,
+
+    /// The exact position of the sweep line, if it is moved with
+    /// [set_exact_position](YStructure::set_exact_position).
+    #[cfg(feature = "exact")]
+    exact_position: Option<ExactPoint2D>,
+}
+
+/// Returns a well distributed priority for a segment id.
+fn priority_of(id: usize) -> u64 {
+    splitmix64(&mut (id as u64))
+}
+
+impl YStructure {
+    /// Returns an empty `YStructure` instance with the sweep line at the origin.
+    pub fn new() -> YStructure {
+        YStructure {
+            ..Default::default()
+        }
+    }
+
+    /// Returns an empty `YStructure` instance, that compares y-coordinates with the given
+    /// tolerance.
+    pub fn with_tolerance(tolerance: Tolerance) -> YStructure {
+        YStructure {
+            tolerance,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the number of segments in the tree.
+    pub fn len(&self) -> usize {
+        self.node_of.len()
+    }
+
+    /// Returns `true` iff there is no segment in the tree.
+    pub fn is_empty(&self) -> bool {
+        self.node_of.is_empty()
+    }
+
+    /// Returns `true` iff a segment with the same id is in the tree.
+    pub fn contains(&self, segment: &LineSegment2D) -> bool {
+        self.node_of.contains_key(&segment.id)
+    }
+
+    /// Returns the current position of the sweep line.
+    pub fn position(&self) -> Point2D {
+        self.position
+    }
+
+    /// Moves the sweep line to a new position, usually the point of the current event.
+    ///
+    /// Moving the sweep line past an intersection of two segments in the tree, without swapping
+    /// them, breaks the order of the tree.
+    pub fn set_position(&mut self, position: Point2D) {
+        self.position = position;
+        #[cfg(feature = "exact")]
+        {
+            self.exact_position = None;
+        }
+    }
+
+    /// Moves the sweep line to an exact position, usually the point of the current event of the
+    /// [exact](crate::exact) kernel. The [position](YStructure::position) is its nearest point.
+    ///
+    /// Until the sweep line is moved with [set_position](YStructure::set_position) again, all
+    /// segments are compared and [reordered](YStructure::reorder) with rational arithmetic.
+    #[cfg(feature = "exact")]
+    pub fn set_exact_position(&mut self, position: ExactPoint2D) {
+        self.position = position.to_point();
+        self.exact_position = Some(position);
+    }
+
+    /// Returns the y-coordinate of a segment at the x-coordinate of the sweep line.
+    pub fn y_of(&self, segment: &LineSegment2D) -> f64 {
+        segment.line().y_from_x(self.position.x).unwrap()
+    }
+
+    /// Compares two segments at the current position of the sweep line.
+    ///
+    /// If one of the segments starts at the sweep line, like every segment that is inserted by
+    /// the sweep, its start point is compared exactly to the other segment with
+    /// [orient2d](crate::tools2d::orient2d). At tie the end point decides, since this is the
+    /// order right of the sweep line. At last the id decides.
+    ///
+    /// Otherwise the segments are ordered after their y-coordinate at the sweep line. If both run
+    /// through the same point within the [tolerance](Tolerance), which allows for rounded
+    /// intersection points, the segment with the smaller slope is below.
+    ///
+    /// At an [exact position](YStructure::set_exact_position) the exact y-coordinates decide and
+    /// at tie the exact slopes, without any tolerance.
+    pub fn compare(&self, a: &LineSegment2D, b: &LineSegment2D) -> Ordering {
+        #[cfg(feature = "exact")]
+        if let Some(position) = &self.exact_position {
+            return exact::y_at(a, &position.x)
+                .cmp(&exact::y_at(b, &position.x))
+                .then_with(|| exact::compare_slopes(a, b))
+                .then_with(|| a.id.cmp(&b.id));
+        }
+
+        let side = if a.p1.x == self.position.x {
+            Some(self.side_of(a, b))
+        } else if b.p1.x == self.position.x {
+            Some(self.side_of(b, a).reverse())
+        } else {
+            None
+        };
+        if let Some(side) = side {
+            return side.then_with(|| a.id.cmp(&b.id));
+        }
+
+        let (y_a, y_b) = (self.y_of(a), self.y_of(b));
+        if !self.tolerance.is_equal(y_a, y_b) {
+            return y_a.total_cmp(&y_b);
+        }
+        self.compare_slopes(a, b).then_with(|| a.id.cmp(&b.id))
+    }
+
+    /// Compares the slopes of two segments, exactly at an
+    /// [exact position](YStructure::set_exact_position).
+    fn compare_slopes(&self, a: &LineSegment2D, b: &LineSegment2D) -> Ordering {
+        #[cfg(feature = "exact")]
+        if self.exact_position.is_some() {
+            return exact::compare_slopes(a, b);
+        }
+        a.line()
+            .slope()
+            .unwrap()
+            .total_cmp(&b.line().slope().unwrap())
+    }
+
+    /// Returns the exact order of a segment, which starts at the sweep line, to another segment.
+    fn side_of(&self, a: &LineSegment2D, b: &LineSegment2D) -> Ordering {
+        let side = |p: &Point2D| orient2d(&b.p1, &b.p2, p).canonical_cmp(&0.0);
+        side(&a.p1).then_with(|| side(&a.p2))
+    }
+
+    /// Inserts a segment at its place at the current position of the sweep line.
+    pub fn insert(&mut self, segment: LineSegment2D) {
+        if self.contains(&segment) {
+            return;
+        }
+
+        let node = Node {
+            segment,
+            priority: priority_of(segment.id),
+            parent: None,
+            left: None,
+            right: None,
+        };
+        let n = match self.free.pop() {
+            Some(n) => {
+                self.nodes[n] = node;
+                n
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        self.node_of.insert(segment.id, n);
+
+        // Insert as leaf like in a binary search tree
+        let mut current = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(n);
+                return;
+            }
+        };
+        loop {
+            let below = self.compare(&segment, &self.nodes[current].segment) == Ordering::Less;
+            let child = if below {
+                self.nodes[current].left
+            } else {
+                self.nodes[current].right
+            };
+            match child {
+                Some(child) => current = child,
+                None => {
+                    if below {
+                        self.nodes[current].left = Some(n);
+                    } else {
+                        self.nodes[current].right = Some(n);
+                    }
+                    self.nodes[n].parent = Some(current);
+                    break;
+                }
+            }
+        }
+
+        // Restore the heap property of the priorities
+        while let Some(parent) = self.nodes[n].parent {
+            if self.nodes[parent].priority >= self.nodes[n].priority {
+                break;
+            }
+            self.rotate_up(n);
+        }
+    }
+
+    /// Removes a segment from the tree, returns `true` iff it was part of the tree.
+    ///
+    /// The segment is found by its id, so the position of the sweep line is not relevant.
+    pub fn remove(&mut self, segment: &LineSegment2D) -> bool {
+        let n = match self.node_of.remove(&segment.id) {
+            Some(n) => n,
+            None => return false,
+        };
+
+        // Rotate the node down until it is a leaf
+        loop {
+            let child = match (self.nodes[n].left, self.nodes[n].right) {
+                (None, None) => break,
+                (Some(left), None) => left,
+                (None, Some(right)) => right,
+                (Some(left), Some(right)) => {
+                    if self.nodes[left].priority > self.nodes[right].priority {
+                        left
+                    } else {
+                        right
+                    }
+                }
+            };
+            self.rotate_up(child);
+        }
+
+        match self.nodes[n].parent {
+            Some(parent) => {
+                if self.nodes[parent].left == Some(n) {
+                    self.nodes[parent].left = None;
+                } else {
+                    self.nodes[parent].right = None;
+                }
+            }
+            None => self.root = None,
+        }
+        self.free.push(n);
+        true
+    }
+
+    /// Reorders a bundle of neighboring segments, which run through the position of the sweep
+    /// line, to their order right of it.
+    ///
+    /// The bundle has to be given from bottom to top. Instead of comparing the y-coordinates,
+    /// which are imprecise at rounded intersection points, the segments are sorted by their slope
+    /// and id and written back into the nodes of the bundle, so the shape of the tree is kept.
+    /// The bundle is sorted in place to the new order, with exact slopes at an
+    /// [exact position](YStructure::set_exact_position).
+    pub fn reorder(&mut self, bundle: &mut [LineSegment2D]) {
+        let nodes: Vec<usize> = bundle.iter().map(|s| self.node_of[&s.id]).collect();
+        bundle.sort_by(|a, b| self.compare_slopes(a, b).then_with(|| a.id.cmp(&b.id)));
+        for (&n, segment) in nodes.iter().zip(bundle.iter()) {
+            self.nodes[n].segment = *segment;
+            self.node_of.insert(segment.id, n);
+        }
+    }
+
+    /// Returns the segment directly above a given segment of the tree.
+    pub fn next(&self, segment: &LineSegment2D) -> Option<LineSegment2D> {
+        let mut current = *self.node_of.get(&segment.id)?;
+        if let Some(right) = self.nodes[current].right {
+            return Some(self.nodes[self.leftmost(right)].segment);
+        }
+        while let Some(parent) = self.nodes[current].parent {
+            if self.nodes[parent].left == Some(current) {
+                return Some(self.nodes[parent].segment);
+            }
+            current = parent;
+        }
+        None
+    }
+
+    /// Returns the segment directly below a given segment of the tree.
+    pub fn prev(&self, segment: &LineSegment2D) -> Option<LineSegment2D> {
+        let mut current = *self.node_of.get(&segment.id)?;
+        if let Some(left) = self.nodes[current].left {
+            return Some(self.nodes[self.rightmost(left)].segment);
+        }
+        while let Some(parent) = self.nodes[current].parent {
+            if self.nodes[parent].right == Some(current) {
+                return Some(self.nodes[parent].segment);
+            }
+            current = parent;
+        }
+        None
+    }
+
+    /// Returns the lowest segment of the tree.
+    pub fn first(&self) -> Option<LineSegment2D> {
+        self.root
+            .map(|root| self.nodes[self.leftmost(root)].segment)
+    }
+
+    /// Returns the lowest segment whose y-coordinate at the sweep line is at least `y`.
+    ///
+    /// The segments are compared exactly to the point at `y` with
+    /// [orient2d](crate::tools2d::orient2d).
+    pub fn first_from(&self, y: f64) -> Option<LineSegment2D> {
+        let p = Point2D {
+            x: self.position.x,
+            y,
+        };
+        let mut current = self.root;
+        let mut found = None;
+        while let Some(n) = current {
+            let segment = self.nodes[n].segment;
+            if orient2d(&segment.p1, &segment.p2, &p) <= 0.0 {
+                found = Some(self.nodes[n].segment);
+                current = self.nodes[n].left;
+            } else {
+                current = self.nodes[n].right;
+            }
+        }
+        found
+    }
+
+    /// Returns the lowest segment that runs through or above an exact point at the sweep line,
+    /// see [first_from](YStructure::first_from).
+    #[cfg(feature = "exact")]
+    pub fn first_from_exact(&self, p: &ExactPoint2D) -> Option<LineSegment2D> {
+        let mut current = self.root;
+        let mut found = None;
+        while let Some(n) = current {
+            let (p1, p2) = exact::endpoints(&self.nodes[n].segment);
+            if !exact::ccw(&p1, &p2, p).is_positive() {
+                found = Some(self.nodes[n].segment);
+                current = self.nodes[n].left;
+            } else {
+                current = self.nodes[n].right;
+            }
+        }
+        found
+    }
+
+    /// Returns all segments of the tree ordered from bottom to top.
+    pub fn segments(&self) -> Vec<LineSegment2D> {
+        let mut segments = Vec::with_capacity(self.len());
+        let mut current = self.first();
+        while let Some(segment) = current {
+            segments.push(segment);
+            current = self.next(&segment);
+        }
+        segments
+    }
+
+    /// Returns the node with the lowest segment of a subtree.
+    fn leftmost(&self, mut n: usize) -> usize {
+        while let Some(left) = self.nodes[n].left {
+            n = left;
+        }
+        n
+    }
+
+    /// Returns the node with the highest segment of a subtree.
+    fn rightmost(&self, mut n: usize) -> usize {
+        while let Some(right) = self.nodes[n].right {
+            n = right;
+        }
+        n
+    }
+
+    /// Rotates a node above its parent, this keeps the order of the segments.
+    fn rotate_up(&mut self, n: usize) {
+        let parent = self.nodes[n].parent.unwrap();
+        let grandparent = self.nodes[parent].parent;
+
+        if self.nodes[parent].left == Some(n) {
+            let inner = self.nodes[n].right;
+            self.nodes[parent].left = inner;
+            self.nodes[n].right = Some(parent);
+            if let Some(inner) = inner {
+                self.nodes[inner].parent = Some(parent);
+            }
+        } else {
+            let inner = self.nodes[n].left;
+            self.nodes[parent].right = inner;
+            self.nodes[n].left = Some(parent);
+            if let Some(inner) = inner {
+                self.nodes[inner].parent = Some(parent);
+            }
+        }
+        self.nodes[parent].parent = Some(n);
+        self.nodes[n].parent = grandparent;
+
+        match grandparent {
+            Some(g) => {
+                if self.nodes[g].left == Some(parent) {
+                    self.nodes[g].left = Some(n);
+                } else {
+                    self.nodes[g].right = Some(n);
+                }
+            }
+            None => self.root = Some(n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_ystructure {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut ys: YStructure = YStructure::new();
+        let mut expected: Vec<LineSegment2D> = Vec::new();
+        for i in 0..100 {
+            let y = ((i * 37) % 100) as f64;
+            let s = LineSegment2D::new(Point2D { x: 0.0, y }, Point2D { x: 1.0, y }).with_id(i);
+            ys.insert(s);
+            expected.push(s);
+        }
+        expected.sort_by(|a, b| a.p1.y.total_cmp(&b.p1.y));
+        assert_eq!(100, ys.len());
+        assert_eq!(expected, ys.segments());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let s0 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 4.0, y: 0.0 });
+        let s1 =
+            LineSegment2D::new(Point2D { x: 0.0, y: 1.0 }, Point2D { x: 4.0, y: 1.0 }).with_id(1);
+        let s2 =
+            LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 4.0, y: 2.0 }).with_id(2);
+        let mut ys: YStructure = YStructure::new();
+        ys.insert(s2);
+        ys.insert(s0);
+        ys.insert(s1);
+
+        assert_eq!(Some(s1), ys.next(&s0));
+        assert_eq!(Some(s2), ys.next(&s1));
+        assert_eq!(None, ys.next(&s2));
+        assert_eq!(Some(s1), ys.prev(&s2));
+        assert_eq!(None, ys.prev(&s0));
+        assert_eq!(Some(s1), ys.first_from(0.5));
+        assert_eq!(Some(s1), ys.first_from(1.0));
+        assert_eq!(None, ys.first_from(2.5));
+
+        assert!(ys.remove(&s1));
+        assert!(!ys.remove(&s1));
+        assert_eq!(Some(s2), ys.next(&s0));
+        assert_eq!(None, ys.next(&s1));
+        assert_eq!(vec![s0, s2], ys.segments());
+    }
+
+    #[test]
+    fn test_swap() {
+        let s0 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let s1 =
+            LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 2.0, y: 0.0 }).with_id(1);
+        let mut ys: YStructure = YStructure::new();
+        ys.insert(s0);
+        ys.insert(s1);
+        assert_eq!(vec![s0, s1], ys.segments());
+
+        // At the intersection the segments are ordered like right of it
+        ys.set_position(Point2D { x: 1.0, y: 1.0 });
+        assert_eq!(Ordering::Less, ys.compare(&s1, &s0));
+        ys.remove(&s0);
+        ys.remove(&s1);
+        ys.insert(s0);
+        ys.insert(s1);
+        assert_eq!(vec![s1, s0], ys.segments());
+    }
+
+    #[test]
+    fn test_reorder() {
+        let s0 = LineSegment2D::new(Point2D { x: 0.0, y: 0.0 }, Point2D { x: 2.0, y: 2.0 });
+        let s1 =
+            LineSegment2D::new(Point2D { x: 0.0, y: 1.0 }, Point2D { x: 2.0, y: 1.0 }).with_id(1);
+        let s2 =
+            LineSegment2D::new(Point2D { x: 0.0, y: 2.0 }, Point2D { x: 2.0, y: 0.0 }).with_id(2);
+        let s3 =
+            LineSegment2D::new(Point2D { x: 0.0, y: 3.0 }, Point2D { x: 2.0, y: 3.0 }).with_id(3);
+        let mut ys: YStructure = YStructure::new();
+        for s in [s3, s1, s0, s2] {
+            ys.insert(s);
+        }
+
+        // The rounded point is slightly off, but the bundle is reversed anyway
+        ys.set_position(Point2D {
+            x: 1.0 - 1e-7,
+            y: 1.0,
+        });
+        let mut bundle = vec![s0, s1, s2];
+        ys.reorder(&mut bundle);
+        assert_eq!(vec![s2, s1, s0], bundle);
+        assert_eq!(vec![s2, s1, s0, s3], ys.segments());
+        assert_eq!(Some(s3), ys.next(&s0));
+        assert_eq!(None, ys.prev(&s2));
+    }
+}
@@ -0,0 +1,260 @@
+//! Vector in a 2-Dimensional vector space.
+//!
+//! Provides a vector struct for the computational geometry library [cg_library](crate).
+
+use crate::point2d::Point;
+use crate::scalar::Scalar;
+use num_traits::Float;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// A vector in a 2-Dimensional vector space.
+///
+/// While a [Point](crate::point2d::Point) is a location, a vector is a direction with a length,
+/// like the difference of two points. Its coordinates are of any
+/// [scalar](crate::scalar::Scalar) type, [Vector2D] is the vector with `f64` coordinates.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::point2d::Point;
+/// use cg_library::vector2d::Vector;
+/// let v: Vector<i64> = Point { x: 3, y: 4 } - Point { x: 1, y: 1 };
+/// assert_eq!(Vector { x: 2, y: 3 }, v);
+/// ```
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vector<T> {
+    /// The x-coordinate.
+    pub x: T,
+    /// The y-coordinate.
+    pub y: T,
+}
+
+/// A vector in a 2-Dimensional vector space with `f64` coordinates.
+///
+/// # Example
+///
+/// ```
+/// use cg_library::vector2d::Vector2D;
+/// let v: Vector2D = Vector2D { x: 3.0, y: 4.0 };
+/// assert_eq!(5.0, v.norm());
+/// ```
+pub type Vector2D = Vector<f64>;
+
+/// This trait allows implicit addition of one vector and another.
+impl<T: Scalar> Add for Vector<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+/// This trait allows implicit substraction of one vector and another.
+impl<T: Scalar> Sub for Vector<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+/// This trait allows a vector to be reversed.
+impl<T: Scalar> Neg for Vector<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+/// This trait allows a vector to be scaled by a scalar.
+impl<T: Scalar> Mul<T> for Vector<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+
+/// This trait allows a vector to be divided by a scalar.
+impl<T: Scalar> Div<T> for Vector<T> {
+    type Output = Self;
+
+    fn div(self, divisor: T) -> Self {
+        Self {
+            x: self.x / divisor,
+            y: self.y / divisor,
+        }
+    }
+}
+
+/// This trait allows a point to be moved by a vector.
+impl<T: Scalar> Add<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn add(self, v: Vector<T>) -> Self {
+        Self {
+            x: self.x + v.x,
+            y: self.y + v.y,
+        }
+    }
+}
+
+/// This trait allows a point to be moved against a vector.
+impl<T: Scalar> Sub<Vector<T>> for Point<T> {
+    type Output = Self;
+
+    fn sub(self, v: Vector<T>) -> Self {
+        Self {
+            x: self.x - v.x,
+            y: self.y - v.y,
+        }
+    }
+}
+
+/// This trait allows a vector to be displayed in the form of `[x,y]`.
+impl<T: Scalar> fmt::Display for Vector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{},{}]", self.x, self.y)
+    }
+}
+
+impl<T: Scalar> Vector<T> {
+    /// Returns the zero vector.
+    pub fn new() -> Vector<T> {
+        Vector {
+            x: T::zero(),
+            y: T::zero(),
+        }
+    }
+
+    /// Returns the dot product with another vector.
+    pub fn dot(self, other: Vector<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Returns the z-coordinate of the cross product with another vector.
+    ///
+    /// The value is positive if the other vector follows in a counter clock wise direction,
+    /// negative if it follows in a clock wise direction and zero if both are parallel.
+    pub fn cross(self, other: Vector<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the squared euclidean length of the vector.
+    pub fn norm_squared(self) -> T {
+        self.dot(self)
+    }
+
+    /// Returns the vector rotated counter clock wise by a right angle.
+    pub fn perpendicular(self) -> Vector<T> {
+        Vector {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+}
+
+impl<T: Scalar + Float> Vector<T> {
+    /// Returns the euclidean length of the vector.
+    pub fn norm(self) -> T {
+        self.norm_squared().sqrt()
+    }
+
+    /// Returns the vector with the same direction and a length of one.
+    ///
+    /// The zero vector has no direction, so its coordinates become `NaN`.
+    pub fn normalize(self) -> Vector<T> {
+        self / self.norm()
+    }
+
+    /// Returns the vector rotated counter clock wise by an angle in radians.
+    pub fn rotate(self, angle: T) -> Vector<T> {
+        let (sin, cos) = angle.sin_cos();
+        Vector {
+            x: self.x * cos - self.y * sin,
+            y: self.x * sin + self.y * cos,
+        }
+    }
+
+    /// Returns the angle in radians from the x-axis to the vector in the range `(-pi,pi]`.
+    pub fn angle(self) -> T {
+        self.y.atan2(self.x)
+    }
+
+    /// Returns the signed angle in radians from the vector to another one in the range
+    /// `(-pi,pi]`, which is positive in counter clock wise direction.
+    pub fn angle_to(self, other: Vector<T>) -> T {
+        self.cross(other).atan2(self.dot(other))
+    }
+}
+
+#[cfg(test)]
+mod test_vector2d {
+    use super::*;
+    use crate::point2d::Point2D;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn test_operators() {
+        let v1: Vector<i64> = Vector { x: 1, y: 2 };
+        let v2: Vector<i64> = Vector { x: 3, y: -1 };
+        assert_eq!(Vector { x: 4, y: 1 }, v1 + v2);
+        assert_eq!(Vector { x: -2, y: 3 }, v1 - v2);
+        assert_eq!(Vector { x: -1, y: -2 }, -v1);
+        assert_eq!(Vector { x: 3, y: 6 }, v1 * 3);
+        assert_eq!(Vector { x: 1, y: 0 }, v2 / 2);
+        assert_eq!("[1,2]", v1.to_string());
+
+        let p: Point<i64> = Point { x: 1, y: 1 };
+        assert_eq!(Point { x: 2, y: 3 }, p + v1);
+        assert_eq!(Point { x: 0, y: -1 }, p - v1);
+        assert_eq!(v1, (p + v1) - p);
+    }
+
+    #[test]
+    fn test_products() {
+        let v1: Vector<i64> = Vector { x: 1, y: 2 };
+        let v2: Vector<i64> = Vector { x: 3, y: -1 };
+        assert_eq!(1, v1.dot(v2));
+        assert_eq!(-7, v1.cross(v2));
+        assert_eq!(7, v2.cross(v1));
+        assert_eq!(5, v1.norm_squared());
+        assert_eq!(Vector { x: -2, y: 1 }, v1.perpendicular());
+        assert_eq!(0, v1.dot(v1.perpendicular()));
+        assert_eq!(0, v1.cross(v1 * 4));
+    }
+
+    #[test]
+    fn test_norm() {
+        let v: Vector2D = Point2D { x: 4.0, y: 5.0 } - Point2D { x: 1.0, y: 1.0 };
+        assert_eq!(5.0, v.norm());
+        assert_eq!(Vector2D { x: 0.6, y: 0.8 }, v.normalize());
+        assert!(Vector2D::new().normalize().x.is_nan());
+    }
+
+    #[test]
+    fn test_angle() {
+        let v: Vector2D = Vector2D { x: 1.0, y: 0.0 };
+        let rotated = v.rotate(FRAC_PI_2);
+        assert!((rotated.x - 0.0).abs() < 1e-12 && (rotated.y - 1.0).abs() < 1e-12);
+        assert_eq!(FRAC_PI_2, Vector2D { x: 0.0, y: 2.0 }.angle());
+        assert_eq!(PI, Vector2D { x: -1.0, y: 0.0 }.angle());
+        assert_eq!(FRAC_PI_2, v.angle_to(Vector2D { x: 0.0, y: 3.0 }));
+        assert_eq!(-FRAC_PI_2, v.angle_to(Vector2D { x: 0.0, y: -3.0 }));
+    }
+}
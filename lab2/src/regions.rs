@@ -1,5 +1,6 @@
 use cg_library::point2d::Point2D;
-use cg_library::polygon2d::Polygon2D;
+use cg_library::polygon2d::{FillRule, PointLocation, Polygon2D};
+use cg_library::transform2d::Transform2D;
 use svg::node::element::path::{Command, Data, Position};
 use svg::node::element::tag;
 use svg::parser::Event;
@@ -18,12 +19,11 @@ const SVG_HEIGHT: f64 = 800.504;
 const GERMANY_WIDTH: f64 = 640.0;
 /// This is the vertical stretch of Germany in km.
 const GERMANY_HEIGHT: f64 = 876.0;
-/// This is the horizontal scaler to get from pixel width to real map width.
-const WIDTH_SCALER: f64 = GERMANY_WIDTH / SVG_WIDTH;
-/// This is the vertical scaler to get from pixel width to real map height.
-const HEIGHT_SCALER: f64 = GERMANY_HEIGHT / SVG_HEIGHT;
-/// This is the area scaler to get from pixel area to real map area.
-const AREA_SCALER: f64 = WIDTH_SCALER * HEIGHT_SCALER;
+
+/// Returns the transformation from `svg` pixels to the real map in km.
+fn pixels_to_km() -> Transform2D {
+    Transform2D::identity().scale(GERMANY_WIDTH / SVG_WIDTH, GERMANY_HEIGHT / SVG_HEIGHT)
+}
 
 #[derive(Debug)]
 pub struct Polygon2DArea {
@@ -36,28 +36,34 @@ pub struct Polygon2DArea {
 
 impl Polygon2DArea {
     /// Returns `true` iff a point `p` is inside the area defined by the borders including holes.
+    ///
+    /// Points on a border belong to the area, points on the border of a hole as well, since the
+    /// hole is the area of another state.
     pub fn contains(&self, p: &Point2D) -> bool {
         for hole in &self.holes {
-            if hole.contains_point(p) {
+            if hole.locate(p) == PointLocation::Inside {
                 return false;
             }
         }
         for border in &self.borders {
-            if border.contains_point(p) {
+            if border.locate(p) != PointLocation::Outside {
                 return true;
             }
         }
         return false;
     }
     /// Returns the area of all borders minus all holes.
+    ///
+    /// The rings are filled with the default `fill-rule` of SVG, so borders that cross themselves
+    /// count every covered region once.
     pub fn calculate_area(&self) -> f64 {
         let mut area: f64 = 0.0;
 
         for hole in &self.holes {
-            area -= hole.calculate_area().abs();
+            area -= hole.area_with(FillRule::NonZero);
         }
         for border in &self.borders {
-            area += border.calculate_area().abs();
+            area += border.area_with(FillRule::NonZero);
         }
         return area;
     }
@@ -68,7 +74,7 @@ pub struct City {
     /// The name of the city.
     name: String,
 
-    /// The position of a city on a `svg` pixel level.
+    /// The position of a city on the real map in km.
     pos: Point2D,
 }
 
@@ -119,6 +125,7 @@ impl Country {
         let mut name = String::new();
         let mut cities: Vec<City> = Vec::new();
         let mut states: Vec<State> = Vec::new();
+        let to_km = pixels_to_km();
 
         for event in svg::open(path, &mut content).expect("Could not open SVG file!") {
             match event {
@@ -149,6 +156,7 @@ impl Country {
                     if group_counter == 1 {
                         let mut poly: Vec<Point2D> = Vec::new();
                         let mut borders: Vec<Polygon2D> = Vec::new();
+                        let mut holes: Vec<Polygon2D> = Vec::new();
 
                         match attributes.get("d") {
                             Some(data) => {
@@ -182,7 +190,30 @@ impl Country {
                                             }),
                                         },
                                         Command::Close => {
-                                            borders.push(Polygon2D::new(poly));
+                                            // Only invalid borders are repaired, every change
+                                            // of their area is printed. Borders that can not be
+                                            // repaired are kept, since their area is still filled
+                                            let border = to_km.apply(&Polygon2D::new(poly));
+                                            if border.is_simple() {
+                                                borders.push(border);
+                                            } else {
+                                                match border.repair() {
+                                                    Ok(report) => {
+                                                        for action in &report.actions {
+                                                            println!(
+                                                                "Border of {}: {}",
+                                                                name, action
+                                                            );
+                                                        }
+                                                        borders.extend(report.polygons);
+                                                        holes.extend(report.holes);
+                                                    }
+                                                    Err(e) => {
+                                                        println!("Border of {}: {}", name, e);
+                                                        borders.push(border);
+                                                    }
+                                                }
+                                            }
                                             poly = Vec::new();
                                         }
                                         _ => {}
@@ -197,10 +228,7 @@ impl Country {
                                 name: String::new(),
                                 pos: Point2D::new(),
                             },
-                            area: Polygon2DArea {
-                                borders,
-                                holes: Vec::new(),
-                            },
+                            area: Polygon2DArea { borders, holes },
                         });
                     }
 
@@ -221,7 +249,7 @@ impl Country {
                         };
                         cities.push(City {
                             name: name.clone(),
-                            pos,
+                            pos: to_km.apply(&pos),
                         });
                     }
                 }
@@ -264,11 +292,13 @@ impl Country {
             println!("\t\tCapital: {}", state.capital.name);
             println!("\t\tBorders: {}", state.area.borders.len());
             println!("\t\tHoles: {}", state.area.holes.len());
-            println!("\t\tArea: {:.1}", state.area.calculate_area());
+            let simple = state.area.borders.iter().filter(|b| b.is_simple()).count();
             println!(
-                "\t\tArea in m²: {:.1}",
-                state.area.calculate_area() * AREA_SCALER
+                "\t\tSimple borders: {}/{}",
+                simple,
+                state.area.borders.len()
             );
+            println!("\t\tArea in km²: {:.1}", state.area.calculate_area());
         }
     }
 }
@@ -7,5 +7,8 @@ fn main() {
 
     println!("Found Intersections: {}", intersections.len());
 
-    save_points(intersections, "intersection_points.dat");
+    save_points(
+        intersections.iter().map(|i| i.point).collect(),
+        "intersection_points.dat",
+    );
 }